@@ -0,0 +1,99 @@
+//! This module parses the command line.
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use crate::report::{Filter, Format};
+use crate::CliError;
+
+/// The text shown when the command line is not understood.
+pub const USAGE: &str = "\
+usage: runtime-contracts report <journal.jsonl> [options]
+
+options:
+  --kind <kind>          only include violations of requires, ensures, or check contracts
+  --module <path>        only include violations within a module, such as bank::accounts
+  --since <time>         only include violations at or after an RFC 3339 time, such as 2024-01-31T12:00:00Z, or date
+  --until <time>         only include violations before an RFC 3339 time
+  --top <count>          how many of the most frequent messages to show for each site (default: 3)
+  --format <format>      table or json (default: table)";
+
+/// A command to run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+  /// Summarize a journal.
+  Report(ReportArgs),
+  /// Show the usage.
+  Help,
+}
+
+/// The arguments of the `report` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportArgs {
+  pub journal: PathBuf,
+  pub filter: Filter,
+  pub top: usize,
+  pub format: Format,
+}
+
+/// Parses the command line, excluding the name of the binary.
+pub fn parse<I>(args: I) -> Result<Command, CliError>
+where
+  I: IntoIterator<Item = String>,
+{
+  let mut args = args.into_iter();
+
+  match args.next().as_deref() {
+    Some("report") => parse_report(args).map(Command::Report),
+    Some("help" | "-h" | "--help") | None => Ok(Command::Help),
+    Some(other) => Err(CliError::Usage(format!("unknown command {other:?}"))),
+  }
+}
+
+fn parse_report(mut args: impl Iterator<Item = String>) -> Result<ReportArgs, CliError> {
+  let mut journal = None;
+  let mut filter = Filter::default();
+  let mut top = 3;
+  let mut format = Format::Table;
+
+  while let Some(arg) = args.next() {
+    let mut value = || {
+      args
+        .next()
+        .ok_or_else(|| CliError::Usage(format!("{arg} needs a value")))
+    };
+
+    match arg.as_str() {
+      "--kind" => filter.kind = Some(parse_value("--kind", &value()?)?),
+      "--module" => filter.module = Some(value()?),
+      "--since" => filter.since = Some(parse_value("--since", &value()?)?),
+      "--until" => filter.until = Some(parse_value("--until", &value()?)?),
+      "--top" => top = parse_value("--top", &value()?)?,
+      "--format" => format = parse_value("--format", &value()?)?,
+      flag if flag.starts_with("--") => {
+        return Err(CliError::Usage(format!("unknown option {flag}")))
+      }
+      path if journal.is_none() => journal = Some(PathBuf::from(path)),
+      extra => return Err(CliError::Usage(format!("unexpected argument {extra:?}"))),
+    }
+  }
+
+  let journal =
+    journal.ok_or_else(|| CliError::Usage("missing the journal to report on".to_string()))?;
+
+  Ok(ReportArgs {
+    journal,
+    filter,
+    top,
+    format,
+  })
+}
+
+fn parse_value<T>(option: &str, value: &str) -> Result<T, CliError>
+where
+  T: FromStr,
+  T::Err: std::fmt::Display,
+{
+  value
+    .parse()
+    .map_err(|err| CliError::Usage(format!("invalid value for {option}: {err}")))
+}
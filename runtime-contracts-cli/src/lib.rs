@@ -0,0 +1,29 @@
+//! Command-line tools for the violation journals written by the [`runtime-contracts`](https://crates.io/crates/runtime-contracts)
+//! crate's `journal` feature. The `runtime-contracts` binary is a thin wrapper around this library.
+//!
+//! ```text
+//! runtime-contracts report <journal.jsonl> [--kind <kind>] [--module <path>] [--since <time>] [--until <time>]
+//!                                          [--top <count>] [--format table|json]
+//! ```
+//!
+//! The `report` command groups the violations in a journal by the contract site responsible, showing how often each site
+//! failed, when it was first and last seen, and its most frequent messages.
+pub mod args;
+pub mod report;
+pub mod time;
+
+use thiserror::Error;
+
+/// The errors which can stop the command-line tools.
+#[derive(Debug, Error)]
+pub enum CliError {
+  #[error("{0}")]
+  Usage(String),
+  #[error("could not read {path}: {source}")]
+  Io {
+    path: String,
+    source: std::io::Error,
+  },
+  #[error("could not render the report: {0}")]
+  Render(#[from] serde_json::Error),
+}
@@ -0,0 +1,59 @@
+use std::fs::File;
+use std::io::{self, BufReader, Write};
+use std::process::ExitCode;
+
+use runtime_contracts_cli::args::{self, Command, ReportArgs, USAGE};
+use runtime_contracts_cli::report;
+use runtime_contracts_cli::CliError;
+
+fn main() -> ExitCode {
+  let result = args::parse(std::env::args().skip(1)).and_then(|command| match command {
+    Command::Report(args) => run_report(args),
+    Command::Help => {
+      println!("{USAGE}");
+
+      Ok(())
+    }
+  });
+
+  match result {
+    Ok(()) => ExitCode::SUCCESS,
+    Err(err @ CliError::Usage(_)) => {
+      eprintln!("error: {err}\n\n{USAGE}");
+
+      ExitCode::from(2)
+    }
+    Err(err) => {
+      eprintln!("error: {err}");
+
+      ExitCode::FAILURE
+    }
+  }
+}
+
+fn run_report(args: ReportArgs) -> Result<(), CliError> {
+  let io_error = |source| CliError::Io {
+    path: args.journal.display().to_string(),
+    source,
+  };
+
+  let file = File::open(&args.journal).map_err(io_error)?;
+  let journal = report::read_journal(BufReader::new(file)).map_err(io_error)?;
+
+  if !journal.invalid_lines.is_empty() {
+    eprintln!(
+      "warning: skipped {} unreadable line(s) of {}: {:?}",
+      journal.invalid_lines.len(),
+      args.journal.display(),
+      journal.invalid_lines
+    );
+  }
+
+  let reports = report::summarize(&journal.records, &args.filter, args.top);
+  let rendered = report::render(&reports, args.format)?;
+
+  // A closed pipe, such as when piping into `head`, is not worth reporting.
+  let _ = io::stdout().write_all(rendered.as_bytes());
+
+  Ok(())
+}
@@ -0,0 +1,315 @@
+//! This module summarizes the violations in a journal by contract site.
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Write};
+use std::io::{self, BufRead};
+use std::str::FromStr;
+
+use runtime_contracts::error::SourceLocation;
+use runtime_contracts::journal::Record;
+use runtime_contracts::ContractKind;
+use serde::{Serialize, Serializer};
+
+use crate::time::Timestamp;
+
+/// Which violations to include in a report. Times are compared as instants, whatever their offset or precision, and
+/// records whose timestamps cannot be parsed are excluded whenever a time bound is set.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Filter {
+  /// Only include violations of this kind of contract.
+  pub kind: Option<ContractKind>,
+  /// Only include violations within this module or its submodules.
+  pub module: Option<String>,
+  /// Only include violations at or after this time.
+  pub since: Option<Timestamp>,
+  /// Only include violations before this time.
+  pub until: Option<Timestamp>,
+}
+
+impl Filter {
+  /// Whether a record should be included.
+  pub fn matches(&self, record: &Record) -> bool {
+    let module_path = record.location().and_then(SourceLocation::module_path);
+    let within = |bound: Option<Timestamp>, holds: fn(Timestamp, Timestamp) -> bool| {
+      bound.is_none_or(|bound| {
+        record
+          .timestamp()
+          .parse()
+          .is_ok_and(|timestamp| holds(timestamp, bound))
+      })
+    };
+
+    self.kind.is_none_or(|kind| record.kind() == kind)
+      && self.module.as_deref().is_none_or(|module| {
+        module_path.is_some_and(|path| {
+          path == module
+            || path
+              .strip_prefix(module)
+              .is_some_and(|rest| rest.starts_with("::"))
+        })
+      })
+      && within(self.since, |timestamp, since| timestamp >= since)
+      && within(self.until, |timestamp, until| timestamp < until)
+  }
+}
+
+/// How to render a report.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+  /// An aligned table for reading in a terminal. This is the default.
+  #[default]
+  Table,
+  /// A JSON array with an object for each site.
+  Json,
+}
+
+impl FromStr for Format {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s.trim().to_ascii_lowercase().as_str() {
+      "table" => Ok(Format::Table),
+      "json" => Ok(Format::Json),
+      _ => Err(format!("unrecognized format: {s:?}")),
+    }
+  }
+}
+
+/// The records read from a journal, along with the numbers of any lines which could not be parsed, such as a line left
+/// incomplete by a crash.
+#[derive(Debug, Default)]
+pub struct Journal {
+  pub records: Vec<Record>,
+  pub invalid_lines: Vec<usize>,
+}
+
+/// Reads the records of a journal, skipping blank lines.
+pub fn read_journal<R>(reader: R) -> io::Result<Journal>
+where
+  R: BufRead,
+{
+  let mut journal = Journal::default();
+
+  for (index, line) in reader.lines().enumerate() {
+    let line = line?;
+
+    if line.trim().is_empty() {
+      continue;
+    }
+
+    match serde_json::from_str(&line) {
+      Ok(record) => journal.records.push(record),
+      Err(_) => journal.invalid_lines.push(index + 1),
+    }
+  }
+
+  Ok(journal)
+}
+
+/// How often a message was seen at a site.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MessageCount {
+  pub message: String,
+  pub count: usize,
+}
+
+/// The violations of a single contract site.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SiteReport {
+  #[serde(serialize_with = "serialize_display")]
+  pub kind: ContractKind,
+  pub file: Option<String>,
+  pub line: Option<u32>,
+  pub column: Option<u32>,
+  pub module_path: Option<String>,
+  pub count: usize,
+  pub first_seen: String,
+  pub last_seen: String,
+  pub top_messages: Vec<MessageCount>,
+}
+
+impl SiteReport {
+  /// Where the site is, in the same form as the crate's errors.
+  pub fn site(&self) -> String {
+    match (&self.file, self.line, self.column) {
+      (Some(file), Some(line), Some(column)) => {
+        let location = SourceLocation::new(file.clone(), line, column);
+
+        match &self.module_path {
+          Some(module_path) => location.with_module_path(module_path.clone()).to_string(),
+          None => location.to_string(),
+        }
+      }
+      _ => "unknown location".to_string(),
+    }
+  }
+}
+
+fn serialize_display<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+  T: fmt::Display,
+  S: Serializer,
+{
+  serializer.collect_str(value)
+}
+
+type SiteKey = (ContractKind, Option<(String, u32, u32)>, Option<String>);
+
+/// The instants at which a site was first and last seen, as far as its records' timestamps can be parsed.
+type Seen = (Option<Timestamp>, Option<Timestamp>);
+
+/// Groups the matching records by site, showing up to `top` messages for each. Sites are ordered from the most to the
+/// least frequently violated. The first and last times a site was seen are compared as instants, and a timestamp which
+/// cannot be parsed is only reported if no other one at the site can.
+pub fn summarize(records: &[Record], filter: &Filter, top: usize) -> Vec<SiteReport> {
+  let mut sites: BTreeMap<SiteKey, (SiteReport, HashMap<&str, usize>, Seen)> = BTreeMap::new();
+
+  for record in records.iter().filter(|record| filter.matches(record)) {
+    let location = record.location();
+    let key = (
+      record.kind(),
+      location.map(|location| {
+        (
+          location.file().to_string(),
+          location.line(),
+          location.column(),
+        )
+      }),
+      location
+        .and_then(SourceLocation::module_path)
+        .map(str::to_string),
+    );
+
+    let time = record.timestamp().parse::<Timestamp>().ok();
+    let (report, messages, (first_seen, last_seen)) =
+      sites
+        .entry(key)
+        .or_insert_with_key(|(kind, position, module_path)| {
+          let report = SiteReport {
+            kind: *kind,
+            file: position.as_ref().map(|(file, _, _)| file.clone()),
+            line: position.as_ref().map(|(_, line, _)| *line),
+            column: position.as_ref().map(|(_, _, column)| *column),
+            module_path: module_path.clone(),
+            count: 0,
+            first_seen: record.timestamp().to_string(),
+            last_seen: record.timestamp().to_string(),
+            top_messages: Vec::new(),
+          };
+
+          (report, HashMap::new(), (time, time))
+        });
+
+    report.count += 1;
+
+    if let Some(time) = time {
+      if first_seen.is_none_or(|first_seen| time < first_seen) {
+        *first_seen = Some(time);
+        report.first_seen = record.timestamp().to_string();
+      }
+
+      if last_seen.is_none_or(|last_seen| time > last_seen) {
+        *last_seen = Some(time);
+        report.last_seen = record.timestamp().to_string();
+      }
+    }
+
+    *messages.entry(record.message()).or_default() += 1;
+  }
+
+  let mut reports: Vec<_> = sites
+    .into_values()
+    .map(|(mut report, messages, _)| {
+      let mut messages: Vec<_> = messages.into_iter().collect();
+      messages.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+
+      report.top_messages = messages
+        .into_iter()
+        .take(top)
+        .map(|(message, count)| MessageCount {
+          message: message.to_string(),
+          count,
+        })
+        .collect();
+
+      report
+    })
+    .collect();
+
+  // The sort is stable, so sites violated equally often stay in the order of their keys.
+  reports.sort_by_key(|report| std::cmp::Reverse(report.count));
+
+  reports
+}
+
+/// Renders a report in the given format.
+pub fn render(reports: &[SiteReport], format: Format) -> Result<String, serde_json::Error> {
+  match format {
+    Format::Table => Ok(render_table(reports)),
+    Format::Json => serde_json::to_string_pretty(reports).map(|json| json + "\n"),
+  }
+}
+
+/// Renders a report as an aligned table.
+pub fn render_table(reports: &[SiteReport]) -> String {
+  if reports.is_empty() {
+    return "no violations found\n".to_string();
+  }
+
+  let header = [
+    "COUNT",
+    "KIND",
+    "SITE",
+    "FIRST SEEN",
+    "LAST SEEN",
+    "TOP MESSAGES",
+  ]
+  .map(str::to_string);
+  let rows: Vec<[String; 6]> = reports
+    .iter()
+    .map(|report| {
+      let messages = report
+        .top_messages
+        .iter()
+        .map(|message| format!("{} ({})", message.message, message.count))
+        .collect::<Vec<_>>()
+        .join("; ");
+
+      [
+        report.count.to_string(),
+        report.kind.to_string(),
+        report.site(),
+        report.first_seen.clone(),
+        report.last_seen.clone(),
+        messages,
+      ]
+    })
+    .collect();
+
+  let mut widths = header.clone().map(|cell| cell.chars().count());
+
+  for row in &rows {
+    for (width, cell) in widths.iter_mut().zip(row) {
+      *width = (*width).max(cell.chars().count());
+    }
+  }
+
+  let mut output = String::new();
+
+  for row in std::iter::once(&header).chain(&rows) {
+    let mut line = String::new();
+
+    for (index, (cell, width)) in row.iter().zip(widths).enumerate() {
+      // Writing to a `String` cannot fail.
+      let _ = match index {
+        0 => write!(line, "{cell:>width$}  "),
+        5 => write!(line, "{cell}"),
+        _ => write!(line, "{cell:<width$}  "),
+      };
+    }
+
+    output.push_str(line.trim_end());
+    output.push('\n');
+  }
+
+  output
+}
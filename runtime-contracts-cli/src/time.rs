@@ -0,0 +1,145 @@
+//! This module parses the RFC 3339 times which bound a report, along with the timestamps of journal records.
+use std::str::FromStr;
+
+/// A point in time, to the millisecond. Times with different offsets or precisions compare as the instants they denote, so
+/// `2024-01-31T12:00:00Z` is the same time as `2024-01-31T12:00:00.000Z` and `2024-01-31T13:00:00+01:00`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+  millis: i64,
+}
+
+impl Timestamp {
+  /// The time a number of milliseconds after the Unix epoch.
+  pub fn from_millis(millis: i64) -> Self {
+    Self { millis }
+  }
+
+  /// The number of milliseconds since the Unix epoch.
+  pub fn millis(&self) -> i64 {
+    self.millis
+  }
+
+  fn parse(s: &str) -> Option<Self> {
+    // Every valid time is ASCII, which also makes slicing by byte safe below.
+    if !s.is_ascii() {
+      return None;
+    }
+
+    let date = s.get(..10)?;
+    let (year, month, day) = (
+      number(&date[..4])?,
+      number(date.get(5..7)?)?,
+      number(date.get(8..10)?)?,
+    );
+
+    if &date[4..5] != "-" || &date[7..8] != "-" || !(1..=12).contains(&month) {
+      return None;
+    }
+
+    if !(1..=days_in_month(year, month)).contains(&day) {
+      return None;
+    }
+
+    let days = days_from_civil(year, month, day);
+
+    // A full date on its own is the start of that day in UTC.
+    if s.len() == 10 {
+      return Some(Self::from_millis(days * 86_400_000));
+    }
+
+    if !matches!(s.get(10..11)?, "T" | "t" | " ") {
+      return None;
+    }
+
+    let time = s.get(11..19)?;
+    let (hour, minute, second) = (
+      number(&time[..2])?,
+      number(time.get(3..5)?)?,
+      number(time.get(6..8)?)?,
+    );
+
+    if &time[2..3] != ":" || &time[5..6] != ":" || hour > 23 || minute > 59 || second > 59 {
+      return None;
+    }
+
+    let mut rest = &s[19..];
+    let mut millis = 0;
+
+    if let Some(fraction) = rest.strip_prefix('.') {
+      let digits = fraction
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(fraction.len());
+
+      if digits == 0 {
+        return None;
+      }
+
+      // Only milliseconds are kept, so any further digits are truncated.
+      let kept = &fraction[..digits.min(3)];
+      millis = number(kept)? * 10_i64.pow(3 - kept.len() as u32);
+      rest = &fraction[digits..];
+    }
+
+    let offset_minutes = match rest {
+      "Z" | "z" => 0,
+      _ => {
+        let sign = match rest.get(..1)? {
+          "+" => 1,
+          "-" => -1,
+          _ => return None,
+        };
+        let (hours, minutes) = (number(rest.get(1..3)?)?, number(rest.get(4..6)?)?);
+
+        if rest.len() != 6 || &rest[3..4] != ":" || hours > 23 || minutes > 59 {
+          return None;
+        }
+
+        sign * (hours * 60 + minutes)
+      }
+    };
+
+    let seconds = days * 86_400 + hour * 3_600 + minute * 60 + second - offset_minutes * 60;
+
+    Some(Self::from_millis(seconds * 1_000 + millis))
+  }
+}
+
+impl FromStr for Timestamp {
+  type Err = String;
+
+  /// Parses an RFC 3339 date-time, such as `2024-01-31T12:00:00Z` or `2024-01-31T13:00:00.250+01:00`, or a full date such
+  /// as `2024-01-31`, which stands for the start of that day in UTC.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Self::parse(s.trim()).ok_or_else(|| format!("not an RFC 3339 time: {s:?}"))
+  }
+}
+
+/// Parses a field made only of ASCII digits.
+fn number(s: &str) -> Option<i64> {
+  if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+    return None;
+  }
+
+  s.parse().ok()
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+  match month {
+    2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+    2 => 28,
+    4 | 6 | 9 | 11 => 30,
+    _ => 31,
+  }
+}
+
+/// The number of days between the Unix epoch and a date in the proleptic Gregorian calendar, using Howard Hinnant's
+/// `days_from_civil` algorithm.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+  let year = if month <= 2 { year - 1 } else { year };
+  let era = year.div_euclid(400);
+  let year_of_era = year - era * 400;
+  let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+  let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+  era * 146_097 + day_of_era - 719_468
+}
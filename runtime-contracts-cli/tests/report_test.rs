@@ -0,0 +1,317 @@
+use std::process::Command as Process;
+
+use pretty_assertions::assert_eq;
+
+use runtime_contracts::journal::Record;
+use runtime_contracts::ContractKind;
+use runtime_contracts_cli::args::{self, Command, ReportArgs};
+use runtime_contracts_cli::report::{self, Filter, Format, MessageCount};
+use runtime_contracts_cli::time::Timestamp;
+
+const JOURNAL: &str = r#"
+{"timestamp":"2024-01-31T10:00:00.000Z","kind":"requires","message":"amount must be positive","location":{"file":"src/accounts.rs","line":10,"column":5,"module_path":"bank::accounts"},"thread":"main","context":{}}
+{"timestamp":"2024-01-31T11:00:00.000Z","kind":"check","message":"balance must not be negative","location":{"file":"src/ledger.rs","line":20,"column":3,"module_path":"bank::ledger"},"thread":"main","context":{}}
+{"timestamp":"2024-01-31T09:00:00.000Z","kind":"requires","message":"amount must be positive","location":{"file":"src/accounts.rs","line":10,"column":5,"module_path":"bank::accounts"},"thread":"worker","context":{"request_id":"7f3a"}}
+{"timestamp":"2024-02-01T08:00:00.000Z","kind":"requires","message":"amount must be below the limit","location":{"file":"src/accounts.rs","line":10,"column":5,"module_path":"bank::accounts"},"thread":null,"context":{}}
+{"timestamp":"2024-02-02T08:00:00.000Z","kind":"ensures","message":"result must be sorted","thread":null,"context":{}}
+{"timestamp":"2024-02-02T08:00:00.000Z","kind":"requ
+"#;
+
+fn records() -> Vec<Record> {
+  let journal = report::read_journal(JOURNAL.as_bytes()).unwrap();
+
+  assert_eq!(journal.invalid_lines, vec![7]);
+
+  journal.records
+}
+
+fn summary(filter: &Filter) -> Vec<(usize, ContractKind, String)> {
+  report::summarize(&records(), filter, 3)
+    .into_iter()
+    .map(|report| (report.count, report.kind, report.site()))
+    .collect()
+}
+
+#[test]
+fn groups_violations_by_site() {
+  let reports = report::summarize(&records(), &Filter::default(), 3);
+  let accounts = &reports[0];
+
+  assert_eq!(reports.len(), 3);
+  assert_eq!(accounts.count, 3);
+  assert_eq!(accounts.site(), "src/accounts.rs:10:5 in bank::accounts");
+  assert_eq!(accounts.first_seen, "2024-01-31T09:00:00.000Z");
+  assert_eq!(accounts.last_seen, "2024-02-01T08:00:00.000Z");
+  assert_eq!(
+    accounts.top_messages,
+    vec![
+      MessageCount {
+        message: "amount must be positive".to_string(),
+        count: 2,
+      },
+      MessageCount {
+        message: "amount must be below the limit".to_string(),
+        count: 1,
+      },
+    ]
+  );
+  assert_eq!(reports[1].kind, ContractKind::Ensures);
+  assert_eq!(reports[1].site(), "unknown location");
+}
+
+#[test]
+fn limits_the_messages_shown() {
+  let reports = report::summarize(&records(), &Filter::default(), 1);
+
+  assert_eq!(reports[0].top_messages.len(), 1);
+  assert_eq!(
+    reports[0].top_messages[0].message,
+    "amount must be positive"
+  );
+}
+
+#[test]
+fn filters_by_kind_module_and_time() {
+  let by_kind = Filter {
+    kind: Some(ContractKind::Check),
+    ..Filter::default()
+  };
+  let by_module = Filter {
+    module: Some("bank".to_string()),
+    ..Filter::default()
+  };
+  let by_exact_module = Filter {
+    module: Some("bank::ledger".to_string()),
+    ..Filter::default()
+  };
+  let by_module_prefix = Filter {
+    module: Some("bank::acc".to_string()),
+    ..Filter::default()
+  };
+  let by_time = Filter {
+    since: Some("2024-01-31T09:30:00Z".parse().unwrap()),
+    until: Some("2024-02-01".parse().unwrap()),
+    ..Filter::default()
+  };
+
+  let ledger = (
+    1,
+    ContractKind::Check,
+    "src/ledger.rs:20:3 in bank::ledger".to_string(),
+  );
+  let accounts = |count| {
+    (
+      count,
+      ContractKind::Requires,
+      "src/accounts.rs:10:5 in bank::accounts".to_string(),
+    )
+  };
+
+  assert_eq!(summary(&by_kind), vec![ledger.clone()]);
+  assert_eq!(summary(&by_module), vec![accounts(3), ledger.clone()]);
+  assert_eq!(summary(&by_exact_module), vec![ledger.clone()]);
+  assert_eq!(summary(&by_module_prefix), vec![]);
+  assert_eq!(summary(&by_time), vec![accounts(1), ledger]);
+}
+
+#[test]
+fn time_bounds_compare_instants_rather_than_strings() {
+  let journal = r#"
+{"timestamp":"2024-01-31T11:59:59.999Z","kind":"check","message":"before"}
+{"timestamp":"2024-01-31T12:00:00.000Z","kind":"check","message":"on the second"}
+{"timestamp":"2024-01-31T12:00:00.500Z","kind":"check","message":"within the second"}
+"#;
+  let records = report::read_journal(journal.as_bytes()).unwrap().records;
+  let messages = |filter: &Filter| -> Vec<&str> {
+    records
+      .iter()
+      .filter(|record| filter.matches(record))
+      .map(|record| record.message())
+      .collect()
+  };
+  let bound = || Some("2024-01-31T12:00:00Z".parse().unwrap());
+
+  let since = Filter {
+    since: bound(),
+    ..Filter::default()
+  };
+  let until = Filter {
+    until: bound(),
+    ..Filter::default()
+  };
+  let offset = Filter {
+    since: Some("2024-01-31T13:00:00.250+01:00".parse().unwrap()),
+    ..Filter::default()
+  };
+
+  assert_eq!(messages(&since), vec!["on the second", "within the second"]);
+  assert_eq!(messages(&until), vec!["before"]);
+  assert_eq!(messages(&offset), vec!["within the second"]);
+}
+
+#[test]
+fn first_and_last_seen_compare_instants_rather_than_strings() {
+  // As strings, the first record sorts last and the second sorts first.
+  let journal = r#"
+{"timestamp":"2024-01-31T12:30:00+01:00","kind":"check","message":"earliest"}
+{"timestamp":"2024-01-31T11:45:00.000Z","kind":"check","message":"latest"}
+{"timestamp":"not a time","kind":"check","message":"unparseable"}
+{"timestamp":"2024-01-31T11:40:00Z","kind":"check","message":"between"}
+"#;
+  let records = report::read_journal(journal.as_bytes()).unwrap().records;
+  let reports = report::summarize(&records, &Filter::default(), 3);
+
+  assert_eq!(reports[0].first_seen, "2024-01-31T12:30:00+01:00");
+  assert_eq!(reports[0].last_seen, "2024-01-31T11:45:00.000Z");
+}
+
+#[test]
+fn parses_rfc_3339_times() {
+  let millis = |s: &str| s.parse::<Timestamp>().map(|timestamp| timestamp.millis());
+
+  assert_eq!(millis("1970-01-01"), Ok(0));
+  assert_eq!(millis("1970-01-01T00:00:01.5Z"), Ok(1_500));
+  assert_eq!(millis("2024-02-29t00:00:00.123456z"), Ok(1_709_164_800_123));
+  assert_eq!(millis("1970-01-01T01:00:00+01:00"), Ok(0));
+  assert_eq!(millis("1969-12-31T23:59:59Z"), Ok(-1_000));
+
+  for invalid in [
+    "",
+    "yesterday",
+    "2024-01-31T12:00",
+    "2024-01-31T12:00:00",
+    "2024-02-30",
+    "2023-02-29T00:00:00Z",
+    "2024-01-31T24:00:00Z",
+    "2024-01-31T12:00:00.Z",
+    "2024-01-31T12:00:00+0100",
+    "2024-01-31T12:00:00é",
+  ] {
+    assert!(
+      invalid.parse::<Timestamp>().is_err(),
+      "{invalid:?} should not parse"
+    );
+  }
+}
+
+#[test]
+fn renders_a_table() {
+  let filter = Filter {
+    module: Some("bank".to_string()),
+    ..Filter::default()
+  };
+  let reports = report::summarize(&records(), &filter, 3);
+
+  assert_eq!(
+    report::render(&reports, Format::Table).unwrap(),
+    [
+      "COUNT  KIND      SITE                                    FIRST SEEN                LAST SEEN                 TOP MESSAGES",
+      "    3  requires  src/accounts.rs:10:5 in bank::accounts  2024-01-31T09:00:00.000Z  2024-02-01T08:00:00.000Z  amount must be positive (2); amount must be below the limit (1)",
+      "    1  check     src/ledger.rs:20:3 in bank::ledger      2024-01-31T11:00:00.000Z  2024-01-31T11:00:00.000Z  balance must not be negative (1)",
+      "",
+    ]
+    .join("\n")
+  );
+  assert_eq!(report::render_table(&[]), "no violations found\n");
+}
+
+#[test]
+fn renders_json() {
+  let filter = Filter {
+    kind: Some(ContractKind::Check),
+    ..Filter::default()
+  };
+  let reports = report::summarize(&records(), &filter, 3);
+  let json: serde_json::Value =
+    serde_json::from_str(&report::render(&reports, Format::Json).unwrap()).unwrap();
+
+  assert_eq!(
+    json,
+    serde_json::json!([{
+      "kind": "check",
+      "file": "src/ledger.rs",
+      "line": 20,
+      "column": 3,
+      "module_path": "bank::ledger",
+      "count": 1,
+      "first_seen": "2024-01-31T11:00:00.000Z",
+      "last_seen": "2024-01-31T11:00:00.000Z",
+      "top_messages": [{ "message": "balance must not be negative", "count": 1 }],
+    }])
+  );
+}
+
+#[test]
+fn parses_the_command_line() {
+  let parse = |line: &str| args::parse(line.split_whitespace().map(str::to_string));
+
+  assert_eq!(
+    parse(
+      "report journal.jsonl --kind requires --module bank --since 2024-01-31 --top 5 --format json"
+    )
+    .unwrap(),
+    Command::Report(ReportArgs {
+      journal: "journal.jsonl".into(),
+      filter: Filter {
+        kind: Some(ContractKind::Requires),
+        module: Some("bank".to_string()),
+        since: Some("2024-01-31".parse().unwrap()),
+        until: None,
+      },
+      top: 5,
+      format: Format::Json,
+    })
+  );
+  assert_eq!(parse("").unwrap(), Command::Help);
+
+  for invalid in [
+    "report",
+    "report journal.jsonl --kind invariant",
+    "report journal.jsonl --format yaml",
+    "report journal.jsonl --top",
+    "report journal.jsonl --since yesterday",
+    "report journal.jsonl --until 2024-01-31T12:00",
+    "report journal.jsonl --verbose",
+    "report a.jsonl b.jsonl",
+    "summarize journal.jsonl",
+  ] {
+    assert!(parse(invalid).is_err(), "{invalid:?} should not parse");
+  }
+}
+
+#[test]
+fn binary_reports_on_a_journal() {
+  let dir = tempfile::tempdir().unwrap();
+  let path = dir.path().join("violations.jsonl");
+  std::fs::write(&path, JOURNAL).unwrap();
+
+  let output = Process::new(env!("CARGO_BIN_EXE_runtime-contracts"))
+    .args(["report", path.to_str().unwrap(), "--kind", "check"])
+    .output()
+    .unwrap();
+  let stdout = String::from_utf8(output.stdout).unwrap();
+
+  assert!(output.status.success());
+  assert_eq!(stdout.lines().count(), 2);
+  assert!(stdout.contains("balance must not be negative (1)"));
+  assert!(String::from_utf8(output.stderr)
+    .unwrap()
+    .contains("skipped 1 unreadable line"));
+
+  let usage = Process::new(env!("CARGO_BIN_EXE_runtime-contracts"))
+    .args(["report"])
+    .output()
+    .unwrap();
+
+  assert_eq!(usage.status.code(), Some(2));
+
+  let invalid_time = Process::new(env!("CARGO_BIN_EXE_runtime-contracts"))
+    .args(["report", path.to_str().unwrap(), "--since", "yesterday"])
+    .output()
+    .unwrap();
+
+  assert_eq!(invalid_time.status.code(), Some(2));
+  assert!(String::from_utf8(invalid_time.stderr)
+    .unwrap()
+    .contains("invalid value for --since: not an RFC 3339 time"));
+}
@@ -0,0 +1,307 @@
+//! Attribute macros for the [`runtime-contracts`](https://crates.io/crates/runtime-contracts) crate.
+//!
+//! These macros expand to the `requires!`, `ensures!`, and `check!` macros, so they only work on functions
+//! returning a `Result` whose error type can be built from a `RuntimeContractError`. You probably want to enable the `macros`
+//! feature of `runtime-contracts` rather than depending on this crate directly.
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, ToTokens};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::visit_mut::{self, VisitMut};
+use syn::{parse_macro_input, Expr, Ident, ItemFn, ReturnType, Token};
+
+/// The arguments accepted by every attribute: a condition, optionally followed by a message, and optionally by the
+/// contract's cost class as `cost = ...`.
+struct ContractArgs {
+  condition: Expr,
+  message: TokenStream2,
+  cost: Option<Expr>,
+}
+
+impl ContractArgs {
+  /// The trailing argument passing the cost class on to the `requires!`, `ensures!`, or `check!` macro.
+  fn cost_arg(&self) -> TokenStream2 {
+    match &self.cost {
+      Some(cost) => quote!(, cost = #cost),
+      None => TokenStream2::new(),
+    }
+  }
+}
+
+impl Parse for ContractArgs {
+  fn parse(input: ParseStream) -> syn::Result<Self> {
+    let mut args: Vec<_> = Punctuated::<Expr, Token![,]>::parse_terminated(input)?
+      .into_iter()
+      .collect();
+
+    let cost = match args.last() {
+      Some(Expr::Assign(assign)) if matches!(&*assign.left, Expr::Path(path) if path.path.is_ident("cost")) =>
+      {
+        let Some(Expr::Assign(assign)) = args.pop() else {
+          unreachable!("the last argument was just matched");
+        };
+
+        Some(*assign.right)
+      }
+      _ => None,
+    };
+    let mut args = args.into_iter();
+
+    let condition = args
+      .next()
+      .ok_or_else(|| input.error("expected a condition, optionally followed by a message"))?;
+    let message = match args.next() {
+      Some(message) => message.into_token_stream(),
+      None => quote!(::core::stringify!(#condition)),
+    };
+
+    if let Some(extra) = args.next() {
+      return Err(syn::Error::new_spanned(
+        extra,
+        "unexpected argument after message",
+      ));
+    }
+
+    Ok(Self {
+      condition,
+      message,
+      cost,
+    })
+  }
+}
+
+/// Replaces every `old(expr)` in a postcondition with a binding holding a clone of `expr` taken before the body runs.
+#[derive(Default)]
+struct OldSnapshots {
+  snapshots: Vec<(Ident, Expr)>,
+}
+
+impl VisitMut for OldSnapshots {
+  fn visit_expr_mut(&mut self, expr: &mut Expr) {
+    if let Expr::Call(call) = expr {
+      let is_old = matches!(&*call.func, Expr::Path(path) if path.qself.is_none() && path.path.is_ident("old"));
+
+      if is_old && call.args.len() == 1 {
+        let ident = quote::format_ident!("__runtime_contracts_old_{}", self.snapshots.len());
+        let mut snapshot = call.args[0].clone();
+
+        // Snapshots may themselves be nested inside `old`, which is harmless but should still be rewritten.
+        self.visit_expr_mut(&mut snapshot);
+        self.snapshots.push((ident.clone(), snapshot));
+        *expr = syn::parse_quote!(#ident);
+
+        return;
+      }
+    }
+
+    visit_mut::visit_expr_mut(self, expr);
+  }
+}
+
+/// Checks that the function can be wrapped, yielding its declared return type.
+fn return_type(function: &ItemFn) -> syn::Result<&syn::Type> {
+  if let Some(asyncness) = &function.sig.asyncness {
+    return Err(syn::Error::new_spanned(
+      asyncness,
+      "contracts are not supported on async functions",
+    ));
+  }
+
+  match &function.sig.output {
+    ReturnType::Type(_, ty) => Ok(ty),
+    ReturnType::Default => Err(syn::Error::new_spanned(
+      &function.sig,
+      "contracts require a function returning a `Result`",
+    )),
+  }
+}
+
+/// Finds the first `impl Trait` within a type.
+#[derive(Default)]
+struct ImplTraitFinder {
+  found: Option<syn::TypeImplTrait>,
+}
+
+impl VisitMut for ImplTraitFinder {
+  fn visit_type_impl_trait_mut(&mut self, ty: &mut syn::TypeImplTrait) {
+    self.found.get_or_insert_with(|| ty.clone());
+  }
+}
+
+/// The return type of a function whose body is run in a closure, which cannot name an `impl Trait` as its return type.
+fn closure_return_type(function: &ItemFn) -> syn::Result<syn::Type> {
+  let mut output = return_type(function)?.clone();
+  let mut finder = ImplTraitFinder::default();
+  finder.visit_type_mut(&mut output);
+
+  match finder.found {
+    Some(impl_trait) => Err(syn::Error::new_spanned(
+      impl_trait,
+      "`impl Trait` is not supported in the return type of a function with an `ensures` or `invariant` contract; \
+       name the type or box it instead",
+    )),
+    None => Ok(output),
+  }
+}
+
+/// Checks a precondition before running the body of a function. The condition is an expression which may refer to the
+/// function's arguments, and the message defaults to the text of the condition. Like every attribute, it accepts the
+/// contract's cost class as a last argument, such as `cost = Cost::Expensive`.
+///
+/// ```ignore
+/// #[requires(i > 0, "i must be greater than 0")]
+/// fn add_one(i: i32) -> runtime_contracts::Result<i32> {
+///   Ok(i + 1)
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn requires(attr: TokenStream, item: TokenStream) -> TokenStream {
+  let args = parse_macro_input!(attr as ContractArgs);
+  let cost = args.cost_arg();
+  let ContractArgs {
+    condition, message, ..
+  } = args;
+  let function = parse_macro_input!(item as ItemFn);
+
+  if let Err(err) = return_type(&function) {
+    return err.to_compile_error().into();
+  }
+
+  let ItemFn {
+    attrs,
+    vis,
+    sig,
+    block,
+  } = function;
+
+  quote! {
+    #(#attrs)*
+    #vis #sig {
+      ::runtime_contracts::requires!(#condition, #message #cost)?;
+
+      #block
+    }
+  }
+  .into()
+}
+
+/// Checks a postcondition against the value returned by a function. Within the condition, `ret` is a reference to the
+/// successful return value. Errors returned by the body are passed through without checking the condition.
+///
+/// The condition may also use `old(expr)` to refer to the value `expr` had _before_ the body ran. Each such expression is
+/// cloned ahead of time, so it must evaluate to a type implementing `Clone`. The body runs in a closure, so the return type
+/// cannot contain `impl Trait`.
+///
+/// ```ignore
+/// #[ensures(*ret > i, "result must be greater than i")]
+/// fn add_one(i: i32) -> runtime_contracts::Result<i32> {
+///   Ok(i + 1)
+/// }
+///
+/// #[ensures(self.balance == old(self.balance) + amount, "balance must grow by exactly amount")]
+/// fn deposit(&mut self, amount: u64) -> runtime_contracts::Result<u64> {
+///   self.balance += amount;
+///
+///   Ok(self.balance)
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn ensures(attr: TokenStream, item: TokenStream) -> TokenStream {
+  let ContractArgs {
+    mut condition,
+    message,
+    cost,
+  } = parse_macro_input!(attr as ContractArgs);
+  let function = parse_macro_input!(item as ItemFn);
+
+  let output = match closure_return_type(&function) {
+    Ok(output) => output,
+    Err(err) => return err.to_compile_error().into(),
+  };
+
+  let ItemFn {
+    attrs,
+    vis,
+    sig,
+    block,
+  } = function;
+
+  let predicate = condition.clone();
+  let cost = cost.map(|cost| quote!(.with_cost(#cost)));
+  let mut old = OldSnapshots::default();
+  old.visit_expr_mut(&mut condition);
+
+  let snapshots = old.snapshots.iter().map(|(ident, snapshot)| {
+    quote! {
+      let #ident = ::core::clone::Clone::clone(&(#snapshot));
+    }
+  });
+
+  quote! {
+    #(#attrs)*
+    #vis #sig {
+      #(#snapshots)*
+
+      #[allow(clippy::redundant_closure_call)]
+      let ret = (|| -> #output #block)()?;
+
+      ::runtime_contracts::__private::ensures(
+        ::runtime_contracts::__site!(#predicate)#cost,
+        ret,
+        |ret| #condition,
+        #message,
+      )
+      .map_err(::core::convert::From::from)
+    }
+  }
+  .into()
+}
+
+/// Checks an invariant both before and after running the body of a function. As with [`macro@ensures`], the return type
+/// cannot contain `impl Trait`.
+///
+/// ```ignore
+/// #[invariant(self.balance >= 0, "balance must never be negative")]
+/// fn withdraw(&mut self, amount: i64) -> runtime_contracts::Result<()> {
+///   self.balance -= amount;
+///
+///   Ok(())
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn invariant(attr: TokenStream, item: TokenStream) -> TokenStream {
+  let args = parse_macro_input!(attr as ContractArgs);
+  let cost = args.cost_arg();
+  let ContractArgs {
+    condition, message, ..
+  } = args;
+  let function = parse_macro_input!(item as ItemFn);
+
+  let output = match closure_return_type(&function) {
+    Ok(output) => output,
+    Err(err) => return err.to_compile_error().into(),
+  };
+
+  let ItemFn {
+    attrs,
+    vis,
+    sig,
+    block,
+  } = function;
+
+  quote! {
+    #(#attrs)*
+    #vis #sig {
+      ::runtime_contracts::check!(#condition, #message #cost)?;
+
+      #[allow(clippy::redundant_closure_call)]
+      let __runtime_contracts_result: #output = (|| -> #output #block)();
+
+      ::runtime_contracts::check!(#condition, #message #cost)?;
+
+      __runtime_contracts_result
+    }
+  }
+  .into()
+}
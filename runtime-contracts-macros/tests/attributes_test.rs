@@ -0,0 +1,148 @@
+use pretty_assertions::assert_eq;
+
+use runtime_contracts::cost::{self, Cost, Gate};
+use runtime_contracts::error::RuntimeContractError;
+use runtime_contracts::macros::{ensures, invariant, requires};
+use runtime_contracts::{ContractKind, Result};
+
+#[requires(i > 0, "i must be greater than 0")]
+fn increment(i: i32) -> Result<i32> {
+  Ok(i + 1)
+}
+
+#[ensures(*ret % 2 == 0, "result must be even")]
+fn double_or_bump(i: u32, bump: bool) -> Result<u32> {
+  if bump {
+    return Ok(i * 2 + 1);
+  }
+
+  Ok(i * 2)
+}
+
+#[ensures(ret.is_empty())]
+fn failing(fail: bool) -> Result<String> {
+  if fail {
+    return Err(RuntimeContractError::new(
+      ContractKind::Check,
+      "body failed",
+    ));
+  }
+
+  Ok(String::new())
+}
+
+#[derive(Debug)]
+enum AppError {
+  Contract(RuntimeContractError),
+}
+
+impl From<RuntimeContractError> for AppError {
+  fn from(err: RuntimeContractError) -> Self {
+    AppError::Contract(err)
+  }
+}
+
+struct Account {
+  balance: i64,
+}
+
+impl Account {
+  #[ensures(self.balance == old(self.balance) + amount, "balance must grow by exactly amount")]
+  fn deposit(&mut self, amount: i64, fee: i64) -> Result<i64> {
+    self.balance += amount - fee;
+
+    Ok(self.balance)
+  }
+
+  #[invariant(self.balance >= 0, "balance must never be negative")]
+  fn withdraw(&mut self, amount: i64) -> core::result::Result<i64, AppError> {
+    self.balance -= amount;
+
+    Ok(self.balance)
+  }
+}
+
+#[test]
+fn requires_attribute_checks_arguments() {
+  assert_eq!(increment(1), Ok(2));
+  let err = increment(0).unwrap_err();
+
+  assert_eq!(err.kind(), ContractKind::Requires);
+  assert_eq!(err.message(), "i must be greater than 0");
+}
+
+#[test]
+fn ensures_attribute_checks_return_value() {
+  assert_eq!(double_or_bump(2, false), Ok(4));
+  let err = double_or_bump(2, true).unwrap_err();
+
+  assert_eq!(err.kind(), ContractKind::Ensures);
+  assert_eq!(err.message(), "result must be even");
+}
+
+#[test]
+fn ensures_attribute_passes_through_errors_and_defaults_message() {
+  assert_eq!(failing(false), Ok(String::new()));
+  let err = failing(true).unwrap_err();
+
+  assert_eq!(err.kind(), ContractKind::Check);
+  assert_eq!(err.message(), "body failed");
+}
+
+#[ensures(ret.len() == old(items.len()) + 1)]
+fn push_one(mut items: Vec<u8>) -> Result<Vec<u8>> {
+  items.push(1);
+
+  Ok(items)
+}
+
+#[test]
+fn ensures_attribute_compares_against_old_values() {
+  let mut account = Account { balance: 10 };
+
+  assert_eq!(account.deposit(5, 0), Ok(15));
+  let err = account.deposit(5, 1).unwrap_err();
+
+  assert_eq!(err.kind(), ContractKind::Ensures);
+  assert_eq!(err.message(), "balance must grow by exactly amount");
+  assert_eq!(account.balance, 19);
+}
+
+#[test]
+fn ensures_attribute_snapshots_moved_arguments() {
+  assert_eq!(push_one(vec![0]), Ok(vec![0, 1]));
+}
+
+#[test]
+fn invariant_attribute_converts_into_custom_error() {
+  let mut account = Account { balance: 10 };
+
+  assert_eq!(account.withdraw(4).ok(), Some(6));
+
+  let err = account.withdraw(7).unwrap_err();
+
+  assert!(matches!(
+    err,
+    AppError::Contract(RuntimeContractError::CheckFailure(_))
+  ));
+}
+
+#[requires(i > 0, "i must be positive", cost = Cost::Expensive)]
+#[ensures(*ret > i, cost = Cost::Expensive)]
+#[invariant(false, cost = Cost::Expensive)]
+fn gated(i: i32) -> Result<i32> {
+  Ok(i - 1)
+}
+
+#[test]
+fn attributes_accept_a_cost() {
+  cost::set_gate(Cost::Expensive, Gate::Never);
+  let skipped = gated(-1);
+  cost::set_gate(Cost::Expensive, Gate::Always);
+
+  assert_eq!(skipped, Ok(-2));
+  assert!(matches!(
+    gated(-1),
+    Err(RuntimeContractError::CheckFailure(_))
+  ));
+}
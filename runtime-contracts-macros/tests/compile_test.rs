@@ -0,0 +1,6 @@
+#[test]
+fn rejects_unsupported_functions() {
+  let cases = trybuild::TestCases::new();
+
+  cases.compile_fail("tests/ui/*.rs");
+}
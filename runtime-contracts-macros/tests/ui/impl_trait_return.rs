@@ -0,0 +1,13 @@
+use runtime_contracts::macros::{ensures, invariant};
+
+#[ensures(true)]
+fn ensured() -> runtime_contracts::Result<impl std::fmt::Display> {
+  Ok(1)
+}
+
+#[invariant(true)]
+fn invariant() -> runtime_contracts::Result<Box<dyn Iterator<Item = impl std::fmt::Display>>> {
+  Ok(Box::new(std::iter::once(1)))
+}
+
+fn main() {}
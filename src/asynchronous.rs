@@ -0,0 +1,283 @@
+//! This module, available with the `async` feature, expresses contracts in asynchronous code. [`requires_async`],
+//! [`ensures_async`], and [`check_async`] take predicates which are futures themselves, such as a lookup confirming that an
+//! account exists, while [`FutureContracts`] checks the output of a future and [`StreamContracts`] checks every item of a
+//! stream, like the adapters of the [`crate::iter`] module do for iterators.
+//!
+//! Nothing here depends on a particular runtime, so the futures can be driven by any executor. A predicate is only awaited
+//! once the contract has been admitted by the configured [`crate::policy`] and [`crate::cost`] gates. As [`crate::context`]
+//! is kept per thread, a violation captures the context of the thread on which its evaluation completed.
+//!
+//! ```
+//! use futures::executor::block_on;
+//! use runtime_contracts::asynchronous::{requires_async, FutureContracts};
+//!
+//! async fn account_exists(id: u32) -> bool {
+//!   id == 7
+//! }
+//!
+//! async fn balance(id: u32) -> i64 {
+//!   if id == 7 { 613 } else { -1 }
+//! }
+//!
+//! block_on(async {
+//!   assert!(requires_async(account_exists(7), "account must exist").await.is_ok());
+//!   assert!(requires_async(account_exists(8), "account must exist").await.is_err());
+//!
+//!   let balance = balance(7).ensures(|balance| *balance >= 0, "balance must not be negative");
+//!   assert_eq!(balance.await, Ok(613));
+//! });
+//! ```
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::{ready, Stream};
+use pin_project_lite::pin_project;
+
+use crate::contract::{self, ContractKind, Site};
+use crate::iter::Position;
+use crate::Result;
+
+/// Evaluates a contract whose predicate is a future. The future is only created, and awaited, once the contract is admitted.
+async fn evaluate<F, P, M>(kind: ContractKind, site: Site, pred: F, message: M) -> Result<()>
+where
+  F: FnOnce() -> P,
+  P: Future<Output = bool>,
+  M: fmt::Display,
+{
+  let Some(policy) = contract::admit(kind, &site, &message) else {
+    return Ok(());
+  };
+
+  let verdict = if pred().await { Ok(()) } else { Err(None) };
+
+  contract::conclude(kind, site, verdict, message, policy)
+}
+
+/// Checks a condition expressed by a future, the asynchronous equivalent of [`crate::requires()`].
+#[track_caller]
+pub fn requires_async<P, M>(pred: P, message: M) -> impl Future<Output = Result<()>>
+where
+  P: Future<Output = bool>,
+  M: fmt::Display,
+{
+  evaluate(ContractKind::Requires, Site::caller(), || pred, message)
+}
+
+/// Checks a condition on a value expressed by a future, yielding the value if it holds. This is the asynchronous equivalent
+/// of [`crate::ensures()`]. The predicate borrows the value only to create the future, which therefore cannot borrow it in
+/// turn: copy whatever the future needs out of the value instead.
+///
+/// ```
+/// use futures::executor::block_on;
+/// use runtime_contracts::asynchronous::ensures_async;
+///
+/// async fn is_registered(email: String) -> bool {
+///   email.ends_with("@example.com")
+/// }
+///
+/// let email = "ada@example.com".to_string();
+/// let email = block_on(ensures_async(email, |email| is_registered(email.clone()), "email must be registered"));
+///
+/// assert_eq!(email.unwrap(), "ada@example.com");
+/// ```
+#[track_caller]
+pub fn ensures_async<T, F, P, M>(
+  value: T,
+  predicate: F,
+  message: M,
+) -> impl Future<Output = Result<T>>
+where
+  F: FnOnce(&T) -> P,
+  P: Future<Output = bool>,
+  M: fmt::Display,
+{
+  let site = Site::caller();
+
+  async move {
+    evaluate(ContractKind::Ensures, site, || predicate(&value), message).await?;
+
+    Ok(value)
+  }
+}
+
+/// Verifies that a condition expressed by a future is met, the asynchronous equivalent of [`crate::check()`].
+#[track_caller]
+pub fn check_async<P, M>(pred: P, message: M) -> impl Future<Output = Result<()>>
+where
+  P: Future<Output = bool>,
+  M: fmt::Display,
+{
+  evaluate(ContractKind::Check, Site::caller(), || pred, message)
+}
+
+/// Checks the output of a future against a contract. It is implemented for every future.
+pub trait FutureContracts: Future + Sized {
+  /// Checks the output of the future against a postcondition once it completes, yielding the output if it holds.
+  #[track_caller]
+  fn ensures<F, M>(self, predicate: F, message: M) -> Ensures<Self, F>
+  where
+    F: FnOnce(&Self::Output) -> bool,
+    M: fmt::Display,
+  {
+    Ensures {
+      future: self,
+      predicate: Some(predicate),
+      message: message.to_string(),
+      site: Site::caller(),
+    }
+  }
+}
+
+impl<Fut: Future> FutureContracts for Fut {}
+
+pin_project! {
+  /// Checks the output of a future against a postcondition. See [`FutureContracts::ensures`].
+  #[must_use = "futures do nothing unless awaited or polled"]
+  pub struct Ensures<Fut, F> {
+    #[pin]
+    future: Fut,
+    predicate: Option<F>,
+    message: String,
+    site: Site,
+  }
+}
+
+impl<Fut, F> Future for Ensures<Fut, F>
+where
+  Fut: Future,
+  F: FnOnce(&Fut::Output) -> bool,
+{
+  type Output = Result<Fut::Output>;
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    let this = self.project();
+    let output = ready!(this.future.poll(cx));
+    let predicate = this
+      .predicate
+      .take()
+      .expect("`Ensures` polled after completion");
+    let result = contract::evaluate(
+      ContractKind::Ensures,
+      *this.site,
+      || predicate(&output),
+      &this.message,
+    );
+
+    Poll::Ready(result.map(|()| output))
+  }
+}
+
+impl<Fut: fmt::Debug, F> fmt::Debug for Ensures<Fut, F> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Ensures")
+      .field("future", &self.future)
+      .field("message", &self.message)
+      .finish_non_exhaustive()
+  }
+}
+
+/// Adapters which check the items of a stream against contracts, like [`crate::iter::IteratorContracts`] does for
+/// iterators. It is implemented for every stream.
+pub trait StreamContracts: Stream + Sized {
+  /// Checks every item against a precondition.
+  #[track_caller]
+  fn requires_each<F, M>(self, predicate: F, message: M) -> Each<Self, F>
+  where
+    F: FnMut(&Self::Item) -> bool,
+    M: fmt::Display,
+  {
+    Each::new(self, ContractKind::Requires, predicate, message)
+  }
+
+  /// Checks every item against a postcondition.
+  #[track_caller]
+  fn ensures_each<F, M>(self, predicate: F, message: M) -> Each<Self, F>
+  where
+    F: FnMut(&Self::Item) -> bool,
+    M: fmt::Display,
+  {
+    Each::new(self, ContractKind::Ensures, predicate, message)
+  }
+
+  /// Checks every item against an invariant.
+  #[track_caller]
+  fn check_each<F, M>(self, predicate: F, message: M) -> Each<Self, F>
+  where
+    F: FnMut(&Self::Item) -> bool,
+    M: fmt::Display,
+  {
+    Each::new(self, ContractKind::Check, predicate, message)
+  }
+}
+
+impl<S: Stream> StreamContracts for S {}
+
+pin_project! {
+  /// Checks every item of a stream against a predicate, yielding the error for the first violation and then ending. A
+  /// violation records the position of the offending item under the [`crate::iter::INDEX`] context key. See
+  /// [`StreamContracts::requires_each`].
+  #[must_use = "streams do nothing unless polled"]
+  pub struct Each<S, F> {
+    #[pin]
+    stream: S,
+    kind: ContractKind,
+    predicate: F,
+    message: String,
+    position: Position,
+  }
+}
+
+impl<S, F> Each<S, F> {
+  #[track_caller]
+  fn new<M>(stream: S, kind: ContractKind, predicate: F, message: M) -> Self
+  where
+    M: fmt::Display,
+  {
+    Self {
+      stream,
+      kind,
+      predicate,
+      message: message.to_string(),
+      position: Position::new(Site::caller()),
+    }
+  }
+}
+
+impl<S, F> Stream for Each<S, F>
+where
+  S: Stream,
+  F: FnMut(&S::Item) -> bool,
+{
+  type Item = Result<S::Item>;
+
+  fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+    let this = self.project();
+
+    if this.position.stopped {
+      return Poll::Ready(None);
+    }
+
+    let Some(item) = ready!(this.stream.poll_next(cx)) else {
+      return Poll::Ready(None);
+    };
+
+    let predicate = this.predicate;
+    let result = this
+      .position
+      .verify(*this.kind, || predicate(&item), &this.message);
+
+    Poll::Ready(Some(result.map(|()| item)))
+  }
+}
+
+impl<S: fmt::Debug, F> fmt::Debug for Each<S, F> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Each")
+      .field("stream", &self.stream)
+      .field("kind", &self.kind)
+      .field("message", &self.message)
+      .finish_non_exhaustive()
+  }
+}
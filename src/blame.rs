@@ -0,0 +1,158 @@
+//! This module assigns blame for violations, in the manner of [Racket's contracts](https://docs.racket-lang.org/guide/contract-boundaries.html).
+//! A contract sits on the boundary between two parties, and whichever side broke its promise is at fault:
+//!
+//! - a violated precondition (`requires`) blames the **caller**, which passed something the function does not accept;
+//! - a violated postcondition (`ensures`) blames the **callee**, which produced something it promised not to;
+//! - a violated invariant (`check`) blames the **module** enclosing the check, whose own state went wrong.
+//!
+//! Every [`RuntimeContractError`](crate::error::RuntimeContractError) exposes its [`Blame`], which names the party at fault
+//! by a label when one is known and otherwise by the module path of the contract, if that is known. A label can be given
+//! to a single contract with [`RuntimeContract::with_blame`](crate::RuntimeContract::with_blame), or to every callee and
+//! module party within a module with [`label_module`], which is handy for routing alerts to the team owning the code.
+//!
+//! ```
+//! use runtime_contracts::blame::{self, Party};
+//! use runtime_contracts::{check, requires};
+//!
+//! blame::label_module(module_path!(), "ledger-team");
+//!
+//! let err = check!(1 > 2, "one must exceed two").unwrap_err();
+//! assert_eq!(err.blame().party(), Party::Module);
+//! assert_eq!(err.blame().label(), Some("ledger-team"));
+//!
+//! // Callers are not identified by the labels of the module they call into.
+//! let err = requires!(1 > 2, "one must exceed two").unwrap_err();
+//! assert_eq!(err.blame().party(), Party::Caller);
+//! assert_eq!(err.blame().label(), None);
+//! # blame::clear_module_labels();
+//! ```
+use std::fmt;
+use std::sync::RwLock;
+
+use crate::contract::ContractKind;
+
+/// The party at fault for a violation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Party {
+  /// The code calling into a function, blamed for violated preconditions.
+  Caller,
+  /// The function itself, blamed for violated postconditions.
+  Callee,
+  /// The module enclosing a check, blamed for violated invariants.
+  Module,
+}
+
+impl Party {
+  /// The party blamed for violations of contracts of the given kind.
+  pub fn for_kind(kind: ContractKind) -> Self {
+    match kind {
+      ContractKind::Requires => Party::Caller,
+      ContractKind::Ensures => Party::Callee,
+      ContractKind::Check => Party::Module,
+    }
+  }
+}
+
+impl fmt::Display for Party {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let name = match self {
+      Party::Caller => "caller",
+      Party::Callee => "callee",
+      Party::Module => "module",
+    };
+
+    f.write_str(name)
+  }
+}
+
+/// Which party is at fault for a violation, and what is known about its identity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blame {
+  party: Party,
+  label: Option<String>,
+  module_path: Option<String>,
+}
+
+impl Blame {
+  /// Assigns blame to a party, identified by an optional label and the module path of the violated contract.
+  pub fn new(party: Party, label: Option<String>, module_path: Option<String>) -> Self {
+    Self {
+      party,
+      label,
+      module_path,
+    }
+  }
+
+  /// The party at fault.
+  pub fn party(&self) -> Party {
+    self.party
+  }
+
+  /// The label identifying the party at fault, if one was given.
+  pub fn label(&self) -> Option<&str> {
+    self.label.as_deref()
+  }
+
+  /// The path of the module containing the violated contract, if known. For a blamed caller this is the module it called
+  /// into rather than its own.
+  pub fn module_path(&self) -> Option<&str> {
+    self.module_path.as_deref()
+  }
+}
+
+impl fmt::Display for Blame {
+  /// Names the party at fault, such as `ledger-team`, `callee bank::accounts`, or `caller of bank::accounts`.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match (&self.label, &self.module_path, self.party) {
+      (Some(label), _, _) => f.write_str(label),
+      (None, Some(module_path), Party::Caller) => write!(f, "caller of {module_path}"),
+      (None, Some(module_path), party) => write!(f, "{party} {module_path}"),
+      (None, None, party) => write!(f, "unknown {party}"),
+    }
+  }
+}
+
+static MODULE_LABELS: RwLock<Vec<(String, String)>> = RwLock::new(Vec::new());
+
+/// Labels the callee and module parties within a module and its submodules. When labels are given for nested modules, the
+/// innermost one applies.
+pub fn label_module<P, L>(module_path: P, label: L)
+where
+  P: Into<String>,
+  L: Into<String>,
+{
+  let module_path = module_path.into();
+  let label = label.into();
+  let mut labels = MODULE_LABELS
+    .write()
+    .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+  labels.retain(|(existing, _)| *existing != module_path);
+  labels.push((module_path, label));
+}
+
+/// Removes every module label.
+pub fn clear_module_labels() {
+  MODULE_LABELS
+    .write()
+    .unwrap_or_else(|poisoned| poisoned.into_inner())
+    .clear();
+}
+
+/// The label of the innermost labelled module containing the given module.
+pub(crate) fn module_label(module_path: &str) -> Option<String> {
+  let labels = MODULE_LABELS
+    .read()
+    .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+  labels
+    .iter()
+    .filter(|(prefix, _)| {
+      module_path == prefix
+        || module_path
+          .strip_prefix(prefix.as_str())
+          .is_some_and(|rest| rest.starts_with("::"))
+    })
+    .max_by_key(|(prefix, _)| prefix.len())
+    .map(|(_, label)| label.clone())
+}
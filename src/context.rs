@@ -0,0 +1,65 @@
+//! This module lets callers attach context, such as a request ID or the name of an operation, to whatever contracts are
+//! violated while it is in scope. Context is kept per thread as a stack of key-value pairs, and a snapshot of it is captured
+//! into every [`crate::error::ContractFailure`] built on that thread.
+//!
+//! ```
+//! use runtime_contracts::{context, requires};
+//!
+//! let _request = context::scope("request_id", 42);
+//! let err = requires(|| false, "must hold").unwrap_err();
+//!
+//! assert_eq!(err.failure().context(), &[("request_id".to_string(), "42".to_string())]);
+//! ```
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::fmt;
+use std::marker::PhantomData;
+
+thread_local! {
+  static STACK: RefCell<Vec<(Cow<'static, str>, String)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Keeps a context entry in scope until dropped. Dropping a guard also ends the scope of any entries added after it.
+#[must_use = "the context entry is removed as soon as the guard is dropped"]
+#[derive(Debug)]
+pub struct ContextGuard {
+  depth: usize,
+  // Context is per thread, so the guard must be dropped on the thread which created it.
+  _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for ContextGuard {
+  fn drop(&mut self) {
+    STACK.with(|stack| stack.borrow_mut().truncate(self.depth));
+  }
+}
+
+/// Adds an entry to the current thread's context until the returned guard is dropped.
+pub fn scope<K, V>(key: K, value: V) -> ContextGuard
+where
+  K: Into<Cow<'static, str>>,
+  V: fmt::Display,
+{
+  STACK.with(|stack| {
+    let mut stack = stack.borrow_mut();
+    let depth = stack.len();
+
+    stack.push((key.into(), value.to_string()));
+
+    ContextGuard {
+      depth,
+      _not_send: PhantomData,
+    }
+  })
+}
+
+/// The entries currently in scope on this thread, from outermost to innermost.
+pub fn current() -> Vec<(String, String)> {
+  STACK.with(|stack| {
+    stack
+      .borrow()
+      .iter()
+      .map(|(key, value)| (key.to_string(), value.clone()))
+      .collect()
+  })
+}
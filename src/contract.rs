@@ -0,0 +1,717 @@
+//! This module contains [`RuntimeContract`], a first-class contract value which can be stored, passed around, and reused
+//! across call sites. The utility functions at the crate root are thin wrappers over the same evaluation logic.
+//!
+//! Contracts form a monoid under [`RuntimeContract::and`] with [`RuntimeContract::always`] as the identity, so they can be
+//! combined freely. When a combined contract is violated, the resulting error names the sub-contract which failed.
+use std::cell::{Cell, OnceCell};
+use std::fmt;
+use std::panic::Location;
+use std::sync::Arc;
+
+use crate::blame;
+use crate::context;
+use crate::cost::{self, Cost};
+use crate::error::{ContractFailure, RuntimeContractError, SourceLocation};
+use crate::hooks;
+use crate::policy::{self, Policy};
+use crate::{Result, RuntimeContractFunction};
+
+/// The kind of a contract, which determines the error variant produced when it is violated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ContractKind {
+  /// A precondition, checked at the _start_ of a function.
+  Requires,
+  /// A postcondition, checked against a value at the _end_ of a function.
+  Ensures,
+  /// An invariant, checked anywhere in control flow.
+  Check,
+}
+
+impl fmt::Display for ContractKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let name = match self {
+      ContractKind::Requires => "requires",
+      ContractKind::Ensures => "ensures",
+      ContractKind::Check => "check",
+    };
+
+    f.write_str(name)
+  }
+}
+
+/// Where and how a contract was expressed. The utility functions only know their caller's location, while the crate's
+/// macros also record the enclosing module and the source text of the predicate.
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct Site {
+  pub(crate) location: &'static Location<'static>,
+  pub(crate) module_path: Option<&'static str>,
+  pub(crate) predicate: Option<&'static str>,
+  pub(crate) cost: Cost,
+  pub(crate) lazy_message: bool,
+  pub(crate) blame: Option<&'static str>,
+  pub(crate) entry: Option<(&'static str, Entry)>,
+}
+
+/// The value of a context entry which a contract records about itself, such as the index of the element it was checked
+/// against. Unlike entries added with [`context::scope`], it is only formatted once the contract fails.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Entry {
+  Index(usize),
+  Name(&'static str),
+}
+
+impl fmt::Display for Entry {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Entry::Index(index) => index.fmt(f),
+      Entry::Name(name) => f.write_str(name),
+    }
+  }
+}
+
+impl Site {
+  /// Captures the location of the caller.
+  #[track_caller]
+  pub fn caller() -> Self {
+    Self {
+      location: Location::caller(),
+      module_path: None,
+      predicate: None,
+      cost: Cost::Cheap,
+      lazy_message: false,
+      blame: None,
+      entry: None,
+    }
+  }
+
+  /// Records the module in which the contract was expressed.
+  pub fn in_module(mut self, module_path: &'static str) -> Self {
+    self.module_path = Some(module_path);
+
+    self
+  }
+
+  /// Records the source text of the predicate.
+  pub fn with_predicate(mut self, predicate: &'static str) -> Self {
+    self.predicate = Some(predicate);
+
+    self
+  }
+
+  /// Records how expensive the contract is to evaluate.
+  pub fn with_cost(mut self, cost: Cost) -> Self {
+    self.cost = cost;
+
+    self
+  }
+
+  /// Records the label of the party to blame when the contract is violated.
+  pub(crate) fn blaming(mut self, label: Option<&'static str>) -> Self {
+    self.blame = label;
+
+    self
+  }
+
+  /// Records a context entry, innermost of all, in case the contract fails.
+  pub(crate) fn with_entry(mut self, key: &'static str, value: Entry) -> Self {
+    self.entry = Some((key, value));
+
+    self
+  }
+
+  /// Marks the contract's message as expensive to produce, so that it is only displayed once the contract is violated.
+  pub(crate) fn with_lazy_message(mut self) -> Self {
+    self.lazy_message = true;
+
+    self
+  }
+
+  /// Builds the details of a failure of a contract of the given kind at this site.
+  pub(crate) fn failure<M>(&self, kind: ContractKind, message: M) -> ContractFailure
+  where
+    M: fmt::Display,
+  {
+    let mut location = SourceLocation::from(self.location);
+
+    if let Some(module_path) = self.module_path {
+      location = location.with_module_path(module_path);
+    }
+
+    let mut failure = ContractFailure::new(message).with_location(location);
+
+    if let Some(predicate) = self.predicate {
+      failure = failure.with_predicate(predicate);
+    }
+
+    for (key, value) in context::current() {
+      failure = failure.with_context(key, value);
+    }
+
+    if let Some((key, value)) = self.entry {
+      failure = failure.with_context(key, value.to_string());
+    }
+
+    // A blamed caller lives outside the contract's module, so it cannot be identified by that module's label.
+    let label = self.blame.map(str::to_string).or_else(|| {
+      self
+        .module_path
+        .filter(|_| kind != ContractKind::Requires)
+        .and_then(blame::module_label)
+    });
+
+    if let Some(label) = label {
+      failure = failure.with_blame_label(label);
+    }
+
+    failure
+  }
+}
+
+/// What became of a single evaluation of a contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Outcome {
+  Passed,
+  Failed,
+  /// The predicate returned an error, so the contract could not be evaluated.
+  Errored,
+  Skipped,
+}
+
+impl fmt::Display for Outcome {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let name = match self {
+      Outcome::Passed => "passed",
+      Outcome::Failed => "failed",
+      Outcome::Errored => "errored",
+      Outcome::Skipped => "skipped",
+    };
+
+    f.write_str(name)
+  }
+}
+
+/// Records the outcome of an evaluation with whichever observers are enabled.
+fn observe(kind: ContractKind, site: &Site, message: &dyn fmt::Display, outcome: Outcome) {
+  #[cfg(feature = "tracing")]
+  crate::trace::evaluated(kind, site, message, outcome);
+
+  crate::stats::record(kind, site, message, outcome);
+}
+
+/// Reports a violation to whichever observers are enabled, before the policy decides what it does.
+#[allow(unused_variables)]
+fn report(error: &RuntimeContractError, policy: Policy) {
+  #[cfg(feature = "tracing")]
+  crate::trace::violated(error);
+
+  #[cfg(feature = "log")]
+  crate::logging::violated(error, policy);
+
+  hooks::notify(error);
+}
+
+/// The outcome of evaluating a predicate. A violation may carry a description of the sub-contract responsible for it.
+pub(crate) type Verdict = core::result::Result<(), Option<String>>;
+
+/// Evaluates a predicate on behalf of a contract of the given kind. Every contract in this crate, whether expressed as a
+/// utility function or as a [`RuntimeContract`] value, is ultimately checked here, subject to the configured [`Policy`].
+pub(crate) fn evaluate<F, M>(kind: ContractKind, site: Site, pred: F, message: M) -> Result<()>
+where
+  F: FnOnce() -> bool,
+  M: fmt::Display,
+{
+  evaluate_with(
+    kind,
+    site,
+    || if pred() { Ok(()) } else { Err(None) },
+    message,
+  )
+}
+
+/// Like [`evaluate`], but for predicates which can name the sub-contract that was violated.
+pub(crate) fn evaluate_with<F, M>(kind: ContractKind, site: Site, pred: F, message: M) -> Result<()>
+where
+  F: FnOnce() -> Verdict,
+  M: fmt::Display,
+{
+  match admit(kind, &site, &message) {
+    Some(policy) => conclude(kind, site, pred(), message, policy),
+    None => Ok(()),
+  }
+}
+
+/// Decides whether a contract of the given kind is evaluated at all, recording it as skipped if not. When it is, yields the
+/// policy deciding what its violation does. This is the first half of [`evaluate_with`], for predicates which cannot be
+/// evaluated on the spot, such as futures.
+pub(crate) fn admit(kind: ContractKind, site: &Site, message: &dyn fmt::Display) -> Option<Policy> {
+  let policy = policy::effective_policy(kind);
+
+  if policy == Policy::Disabled || !cost::admits(site.cost, site.location) {
+    observe(kind, site, message, Outcome::Skipped);
+
+    return None;
+  }
+
+  Some(policy)
+}
+
+/// Concludes the evaluation of a contract which was admitted under the given policy, now that its predicate has returned a
+/// verdict. This is the second half of [`evaluate_with`].
+pub(crate) fn conclude<M>(
+  kind: ContractKind,
+  site: Site,
+  verdict: Verdict,
+  message: M,
+  policy: Policy,
+) -> Result<()>
+where
+  M: fmt::Display,
+{
+  let failure = match verdict {
+    Ok(()) => {
+      observe(kind, &site, &message, Outcome::Passed);
+
+      return Ok(());
+    }
+    Err(None) => site.failure(kind, &message),
+    Err(Some(violated)) => site.failure(kind, format!("{message} (violated: {violated})")),
+  };
+
+  let error = RuntimeContractError::from_failure(kind, failure);
+
+  fail(kind, &site, &message, error, policy)
+}
+
+/// Like [`evaluate`], but for predicates which can fail on their own. A contract whose predicate fails could not be
+/// evaluated, which is reported and enforced like a violation, but yields an error recording the predicate's own error.
+pub(crate) fn try_evaluate<F, E, M>(
+  kind: ContractKind,
+  site: Site,
+  pred: F,
+  message: M,
+) -> Result<()>
+where
+  F: FnOnce() -> core::result::Result<bool, E>,
+  E: Into<Box<dyn std::error::Error + Send + Sync>>,
+  M: fmt::Display,
+{
+  let Some(policy) = admit(kind, &site, &message) else {
+    return Ok(());
+  };
+
+  match pred() {
+    Ok(holds) => {
+      let verdict = if holds { Ok(()) } else { Err(None) };
+
+      conclude(kind, site, verdict, message, policy)
+    }
+    Err(source) => {
+      let error = RuntimeContractError::unevaluated(kind, site.failure(kind, &message), source);
+
+      fail(kind, &site, &message, error, policy)
+    }
+  }
+}
+
+/// Records and reports a contract which did not hold, or could not be evaluated, then enforces the policy on its error.
+fn fail(
+  kind: ContractKind,
+  site: &Site,
+  message: &dyn fmt::Display,
+  error: RuntimeContractError,
+  policy: Policy,
+) -> Result<()> {
+  let outcome = if error.is_violation() {
+    Outcome::Failed
+  } else {
+    Outcome::Errored
+  };
+
+  observe(kind, site, message, outcome);
+  report(&error, policy);
+
+  policy.enforce(error)
+}
+
+/// Evaluates a predicate on behalf of a contract of the given kind, producing a caller-defined error if it does not hold.
+/// The error is only built on failure, and its `Display` output serves as the contract's message.
+pub(crate) fn evaluate_or<F, G, E>(kind: ContractKind, site: Site, pred: F, err: G) -> Result<(), E>
+where
+  F: FnOnce() -> bool,
+  G: FnOnce() -> E,
+  E: fmt::Display,
+{
+  let err = LazyError::new(err);
+
+  evaluate(kind, site.with_lazy_message(), pred, &err).map_err(|_| err.into_inner())
+}
+
+/// A caller-defined error which is built the first time it is needed, either for display or to be returned.
+struct LazyError<G, E> {
+  build: Cell<Option<G>>,
+  error: OnceCell<E>,
+}
+
+impl<G, E> LazyError<G, E>
+where
+  G: FnOnce() -> E,
+{
+  fn new(build: G) -> Self {
+    Self {
+      build: Cell::new(Some(build)),
+      error: OnceCell::new(),
+    }
+  }
+
+  fn get(&self) -> &E {
+    self.error.get_or_init(|| {
+      let build = self.build.take().expect("error builder is only taken once");
+
+      build()
+    })
+  }
+
+  fn into_inner(self) -> E {
+    self.get();
+
+    self.error.into_inner().expect("error was just built")
+  }
+}
+
+impl<G, E> fmt::Display for LazyError<G, E>
+where
+  G: FnOnce() -> E,
+  E: fmt::Display,
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    self.get().fmt(f)
+  }
+}
+
+/// A predicate which, when it does not hold, describes the innermost contract responsible.
+type Predicate<T> = Arc<dyn Fn(&T) -> core::result::Result<(), String> + Send + Sync>;
+
+/// A contract expressed as a value. It bundles a predicate, a message, and a [`ContractKind`] so that it can be defined once
+/// and verified wherever it is needed. Cloning a contract is cheap since the predicate is shared.
+///
+/// # Examples
+///
+/// ```
+/// use runtime_contracts::contract::RuntimeContract;
+///
+/// let positive = RuntimeContract::requires(|i: &i32| *i > 0, "must be positive");
+///
+/// assert!(positive.verify(&5).is_ok());
+/// assert!(positive.verify(&-5).is_err());
+///
+/// // Contracts can also pass values through, just like `ensures`.
+/// assert_eq!(positive.apply(7), Ok(7));
+/// ```
+///
+/// Contracts can be composed with combinators such as [`RuntimeContract::and`] and [`RuntimeContract::or`]:
+///
+/// ```
+/// use runtime_contracts::contract::RuntimeContract;
+///
+/// let positive = RuntimeContract::requires(|i: &i32| *i > 0, "must be positive");
+/// let even = RuntimeContract::requires(|i: &i32| i % 2 == 0, "must be even");
+/// let positive_and_even = positive.and(even);
+///
+/// assert!(positive_and_even.verify(&4).is_ok());
+/// assert_eq!(
+///   positive_and_even.verify(&3).unwrap_err().message(),
+///   "must be positive and must be even (violated: must be even)"
+/// );
+/// ```
+pub struct RuntimeContract<T: ?Sized> {
+  kind: ContractKind,
+  message: String,
+  cost: Cost,
+  blame: Option<&'static str>,
+  predicate: Predicate<T>,
+}
+
+impl<T: ?Sized> RuntimeContract<T> {
+  /// Creates a new contract of the given kind from a predicate and an error message.
+  pub fn new<F, M>(kind: ContractKind, predicate: F, message: M) -> Self
+  where
+    F: Fn(&T) -> bool + Send + Sync + 'static,
+    M: fmt::Display,
+  {
+    let message = message.to_string();
+    let violated = message.clone();
+
+    Self {
+      kind,
+      cost: Cost::Cheap,
+      blame: None,
+      message,
+      predicate: Arc::new(move |value| {
+        if predicate(value) {
+          Ok(())
+        } else {
+          Err(violated.clone())
+        }
+      }),
+    }
+  }
+
+  /// Creates a contract which always holds. It is the identity of [`RuntimeContract::and`].
+  pub fn always() -> Self {
+    Self {
+      kind: ContractKind::Check,
+      cost: Cost::Cheap,
+      blame: None,
+      message: "always".to_string(),
+      predicate: Arc::new(|_| Ok(())),
+    }
+  }
+
+  /// Creates a precondition, the value equivalent of [`crate::requires()`].
+  pub fn requires<F, M>(predicate: F, message: M) -> Self
+  where
+    F: Fn(&T) -> bool + Send + Sync + 'static,
+    M: fmt::Display,
+  {
+    Self::new(ContractKind::Requires, predicate, message)
+  }
+
+  /// Creates a postcondition, the value equivalent of [`crate::ensures()`].
+  pub fn ensures<F, M>(predicate: F, message: M) -> Self
+  where
+    F: Fn(&T) -> bool + Send + Sync + 'static,
+    M: fmt::Display,
+  {
+    Self::new(ContractKind::Ensures, predicate, message)
+  }
+
+  /// Creates an invariant, the value equivalent of [`crate::check()`].
+  pub fn check<F, M>(predicate: F, message: M) -> Self
+  where
+    F: Fn(&T) -> bool + Send + Sync + 'static,
+    M: fmt::Display,
+  {
+    Self::new(ContractKind::Check, predicate, message)
+  }
+
+  /// The kind of this contract.
+  pub fn kind(&self) -> ContractKind {
+    self.kind
+  }
+
+  /// The message reported when this contract is violated.
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+
+  /// How expensive this contract is to evaluate. Combined contracts are as expensive as their most expensive part.
+  pub fn cost(&self) -> Cost {
+    self.cost
+  }
+
+  /// The label identifying the party blamed when this contract is violated, if one was given.
+  pub fn blame(&self) -> Option<&'static str> {
+    self.blame
+  }
+
+  /// Labels the party blamed when this contract is violated, which is the caller for a precondition, the callee for a
+  /// postcondition, and the enclosing module for an invariant. See [`crate::blame`].
+  pub fn with_blame(mut self, label: &'static str) -> Self {
+    self.blame = Some(label);
+
+    self
+  }
+
+  /// Tags this contract with a cost class, which determines the [`crate::cost::Gate`] deciding whether it is evaluated.
+  pub fn with_cost(mut self, cost: Cost) -> Self {
+    self.cost = cost;
+
+    self
+  }
+
+  /// Replaces the message reported when this contract is violated. This is most useful for naming combined contracts.
+  pub fn with_message<M>(mut self, message: M) -> Self
+  where
+    M: fmt::Display,
+  {
+    self.message = message.to_string();
+
+    self
+  }
+
+  /// Verifies the contract against the given value, yielding an error of the appropriate kind if it does not hold. When
+  /// the violation was caused by a sub-contract, the error message names it.
+  #[track_caller]
+  pub fn verify(&self, value: &T) -> Result<()> {
+    self.verify_at(Site::caller(), value)
+  }
+
+  fn verify_at(&self, site: Site, value: &T) -> Result<()> {
+    self.verify_as(self.kind, site, value)
+  }
+
+  /// Verifies the contract as if it were of the given kind, which determines the error produced and the party blamed.
+  pub(crate) fn verify_as(&self, kind: ContractKind, site: Site, value: &T) -> Result<()> {
+    let verdict =
+      || (self.predicate)(value).map_err(|violated| (violated != self.message).then_some(violated));
+
+    let site = site.with_cost(self.cost).blaming(self.blame);
+
+    evaluate_with(kind, site, verdict, &self.message)
+  }
+}
+
+impl<T: ?Sized + 'static> RuntimeContract<T> {
+  /// Combines all of the given contracts with [`RuntimeContract::and`]. The combined contract takes the kind of the first
+  /// contract and holds trivially when there are none.
+  pub fn all_of<I>(contracts: I) -> Self
+  where
+    I: IntoIterator<Item = Self>,
+  {
+    contracts
+      .into_iter()
+      .reduce(Self::and)
+      .unwrap_or_else(Self::always)
+  }
+
+  /// Combines all of the given contracts with [`RuntimeContract::or`]. The combined contract takes the kind of the first
+  /// contract and, since none of them can hold, is violated when there are none.
+  pub fn any_of<I>(contracts: I) -> Self
+  where
+    I: IntoIterator<Item = Self>,
+  {
+    contracts
+      .into_iter()
+      .reduce(Self::or)
+      .unwrap_or_else(|| Self::always().not())
+  }
+
+  /// Combines two contracts into one which holds only when both do. The second contract is only evaluated if the first
+  /// one holds.
+  pub fn and(self, other: Self) -> Self {
+    let message = format!("{} and {}", self.message, other.message);
+    let (first, second) = (self.predicate, other.predicate);
+
+    Self {
+      kind: self.kind,
+      cost: self.cost.max(other.cost),
+      blame: self.blame.or(other.blame),
+      message,
+      predicate: Arc::new(move |value| first(value).and_then(|()| second(value))),
+    }
+  }
+
+  /// Combines two contracts into one which holds when either does. The second contract is only evaluated if the first
+  /// one is violated.
+  pub fn or(self, other: Self) -> Self {
+    let message = format!("{} or {}", self.message, other.message);
+    let violated = message.clone();
+    let (first, second) = (self.predicate, other.predicate);
+
+    Self {
+      kind: self.kind,
+      cost: self.cost.max(other.cost),
+      blame: self.blame.or(other.blame),
+      message,
+      predicate: Arc::new(move |value| {
+        first(value)
+          .or_else(|_| second(value))
+          .map_err(|_| violated.clone())
+      }),
+    }
+  }
+
+  /// Negates a contract, producing one which holds only when the original is violated.
+  #[allow(clippy::should_implement_trait)]
+  pub fn not(self) -> Self {
+    let message = format!("not ({})", self.message);
+    let violated = message.clone();
+    let inner = self.predicate;
+
+    Self {
+      kind: self.kind,
+      cost: self.cost,
+      blame: self.blame,
+      message,
+      predicate: Arc::new(move |value| match inner(value) {
+        Ok(()) => Err(violated.clone()),
+        Err(_) => Ok(()),
+      }),
+    }
+  }
+
+  /// Combines two contracts into one expressing logical implication: whenever the first contract holds, the second one
+  /// must hold as well.
+  pub fn implies(self, other: Self) -> Self {
+    let message = format!("{} implies {}", self.message, other.message);
+    let (antecedent, consequent) = (self.predicate, other.predicate);
+
+    Self {
+      kind: self.kind,
+      cost: self.cost.max(other.cost),
+      blame: self.blame.or(other.blame),
+      message,
+      predicate: Arc::new(move |value| match antecedent(value) {
+        Ok(()) => consequent(value),
+        Err(_) => Ok(()),
+      }),
+    }
+  }
+}
+
+impl<T> RuntimeContract<T> {
+  /// Verifies the contract against the given value and, if it holds, yields the value back. This mirrors [`crate::ensures()`].
+  #[track_caller]
+  pub fn apply(&self, value: T) -> Result<T> {
+    self.verify(&value)?;
+
+    Ok(value)
+  }
+}
+
+impl<T: 'static> RuntimeContract<T> {
+  /// Converts this contract into a boxed [`RuntimeContractFunction`] which applies the contract to its argument.
+  ///
+  /// ```
+  /// use runtime_contracts::contract::RuntimeContract;
+  ///
+  /// let even = RuntimeContract::check(|i: &u32| i % 2 == 0, "must be even").into_fn();
+  ///
+  /// assert_eq!(even(4), Ok(4));
+  /// assert!(even(3).is_err());
+  /// ```
+  #[track_caller]
+  pub fn into_fn(self) -> Box<RuntimeContractFunction<T>> {
+    let site = Site::caller();
+
+    Box::new(move |value| {
+      self.verify_at(site, &value)?;
+
+      Ok(value)
+    })
+  }
+}
+
+impl<T: ?Sized> Clone for RuntimeContract<T> {
+  fn clone(&self) -> Self {
+    Self {
+      kind: self.kind,
+      cost: self.cost,
+      blame: self.blame,
+      message: self.message.clone(),
+      predicate: Arc::clone(&self.predicate),
+    }
+  }
+}
+
+impl<T: ?Sized> fmt::Debug for RuntimeContract<T> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("RuntimeContract")
+      .field("kind", &self.kind)
+      .field("message", &self.message)
+      .field("cost", &self.cost)
+      .field("blame", &self.blame)
+      .finish_non_exhaustive()
+  }
+}
@@ -0,0 +1,200 @@
+//! This module lets contracts be tagged with a [`Cost`] class so that expensive ones can be sampled or switched off without
+//! affecting cheap ones. Each class has a [`Gate`] deciding which evaluations actually run. By default every class is
+//! [`Gate::Always`], so contracts run unconditionally unless configured otherwise.
+//!
+//! Contracts are cheap unless tagged otherwise: contract values with [`RuntimeContract::with_cost`], and contracts
+//! expressed with the crate's macros, including the attribute macros, with a `cost = ...` argument following the message.
+//!
+//! [`RuntimeContract::with_cost`]: crate::RuntimeContract::with_cost
+//!
+//! Evaluations skipped by a gate are counted per class, so that the proportion of contracts actually checked stays known.
+use std::cell::Cell;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::panic::Location;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+/// How expensive a contract's predicate is to evaluate.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Cost {
+  /// A predicate which is cheap enough to always evaluate, such as a comparison. This is the default.
+  #[default]
+  Cheap,
+  /// A predicate with noticeable but bounded cost, such as a lookup.
+  Moderate,
+  /// A predicate which is costly to evaluate, such as re-sorting a collection to confirm a result.
+  Expensive,
+}
+
+impl Cost {
+  const ALL: [Cost; 3] = [Cost::Cheap, Cost::Moderate, Cost::Expensive];
+}
+
+impl fmt::Display for Cost {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let name = match self {
+      Cost::Cheap => "cheap",
+      Cost::Moderate => "moderate",
+      Cost::Expensive => "expensive",
+    };
+
+    f.write_str(name)
+  }
+}
+
+/// Which evaluations of contracts in a cost class actually run.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Gate {
+  /// Run every evaluation. This is the default.
+  #[default]
+  Always,
+  /// Run each evaluation with the given probability, between `0.0` and `1.0`.
+  Sample(f64),
+  /// Run the first of every `n` evaluations of each contract. Evaluations are counted per call site, so contracts sharing
+  /// a class are each checked regularly however their calls interleave. A value of `0` behaves like [`Gate::Never`].
+  EveryNth(u64),
+  /// Run no evaluations.
+  Never,
+}
+
+const TAG_SHIFT: u32 = 56;
+const PAYLOAD_MASK: u64 = (1 << TAG_SHIFT) - 1;
+const SAMPLE_SCALE: f64 = (1u64 << 32) as f64;
+
+impl Gate {
+  /// Packs the gate into a single word so that it can be swapped atomically.
+  fn encode(self) -> u64 {
+    let (tag, payload) = match self {
+      Gate::Always => (0, 0),
+      Gate::Sample(probability) => (1, (probability.clamp(0.0, 1.0) * SAMPLE_SCALE) as u64),
+      Gate::EveryNth(n) => (2, n.min(PAYLOAD_MASK)),
+      Gate::Never => (3, 0),
+    };
+
+    (tag << TAG_SHIFT) | payload
+  }
+
+  fn decode(word: u64) -> Self {
+    let payload = word & PAYLOAD_MASK;
+
+    match word >> TAG_SHIFT {
+      0 => Gate::Always,
+      1 => Gate::Sample(payload as f64 / SAMPLE_SCALE),
+      2 => Gate::EveryNth(payload),
+      _ => Gate::Never,
+    }
+  }
+}
+
+/// The gate and skip count of a single cost class.
+struct CostClass {
+  gate: AtomicU64,
+  skipped: AtomicU64,
+}
+
+impl CostClass {
+  const fn new() -> Self {
+    Self {
+      gate: AtomicU64::new(0),
+      skipped: AtomicU64::new(0),
+    }
+  }
+}
+
+static CLASSES: [CostClass; 3] = [CostClass::new(), CostClass::new(), CostClass::new()];
+
+fn class(cost: Cost) -> &'static CostClass {
+  &CLASSES[cost as usize]
+}
+
+// The number of calls made at each site whose class is gated by `Gate::EveryNth`, keyed by the address of the site's
+// `Location`, which is stable for a given call site.
+static CALLS: RwLock<Option<HashMap<usize, AtomicU64>>> = RwLock::new(None);
+
+/// Counts a call at a site, yielding how many calls were made there before it.
+fn count_call(location: &'static Location<'static>) -> u64 {
+  let address = location as *const _ as usize;
+
+  {
+    let calls = CALLS
+      .read()
+      .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+    if let Some(count) = calls.as_ref().and_then(|calls| calls.get(&address)) {
+      return count.fetch_add(1, Ordering::Relaxed);
+    }
+  }
+
+  let mut calls = CALLS
+    .write()
+    .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+  calls
+    .get_or_insert_with(HashMap::new)
+    .entry(address)
+    .or_default()
+    .fetch_add(1, Ordering::Relaxed)
+}
+
+/// The gate currently configured for a cost class.
+pub fn gate(cost: Cost) -> Gate {
+  Gate::decode(class(cost).gate.load(Ordering::Relaxed))
+}
+
+/// Configures the gate for a cost class.
+pub fn set_gate(cost: Cost, gate: Gate) {
+  class(cost).gate.store(gate.encode(), Ordering::Relaxed);
+}
+
+/// How many evaluations of contracts in a cost class have been skipped by its gate.
+pub fn skipped(cost: Cost) -> u64 {
+  class(cost).skipped.load(Ordering::Relaxed)
+}
+
+/// Resets the skip counts of every cost class to zero.
+pub fn reset_skipped() {
+  for cost in Cost::ALL {
+    class(cost).skipped.store(0, Ordering::Relaxed);
+  }
+}
+
+/// Decides whether an evaluation of a contract in the given cost class, at the given call site, should run, counting it if
+/// not.
+pub(crate) fn admits(cost: Cost, location: &'static Location<'static>) -> bool {
+  let class = class(cost);
+
+  let admitted = match Gate::decode(class.gate.load(Ordering::Relaxed)) {
+    Gate::Always => true,
+    Gate::Never => false,
+    Gate::EveryNth(0) => false,
+    Gate::EveryNth(n) => count_call(location).is_multiple_of(n),
+    Gate::Sample(probability) => random_unit() < probability,
+  };
+
+  if !admitted {
+    class.skipped.fetch_add(1, Ordering::Relaxed);
+  }
+
+  admitted
+}
+
+thread_local! {
+  static RNG_STATE: Cell<u64> = Cell::new(RandomState::new().build_hasher().finish() | 1);
+}
+
+/// Yields a pseudo-random number in `[0, 1)`. This is a xorshift generator, which is plenty for sampling.
+fn random_unit() -> f64 {
+  RNG_STATE.with(|state| {
+    let mut x = state.get();
+
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    state.set(x);
+
+    (x >> 11) as f64 / (1u64 << 53) as f64
+  })
+}
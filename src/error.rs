@@ -1,13 +1,426 @@
 //! This module contains the crate's own error type. It can hold other error-related data/logic as needed.
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
 use thiserror::Error;
 
+use crate::blame::{Blame, Party};
+use crate::contract::ContractKind;
+
 /// The error type for returning information about contract failures at runtime.
-#[derive(Error, Debug, PartialEq)]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum RuntimeContractError {
   #[error("requires validation failed: {0}")]
-  RequiresFailure(String),
+  RequiresFailure(ContractFailure),
   #[error("ensures validation failed: {0}")]
-  EnsuresFailure(String),
-  #[error("check validation failed: {0}")]
-  CheckFailure(String),
+  EnsuresFailure(ContractFailure),
+  #[error("check validation failed: invariant violated: {0}")]
+  CheckFailure(ContractFailure),
+  /// The predicate of a contract returned an error of its own, so the contract could not be evaluated at all. The
+  /// predicate's error is the [`source`](Error::source) of this one.
+  #[error("{kind} validation could not be performed: {failure}")]
+  EvaluationFailure {
+    kind: ContractKind,
+    failure: Box<ContractFailure>,
+    source: PredicateError,
+  },
+}
+
+impl RuntimeContractError {
+  /// Creates an error for a violated contract of the given kind, without any source location.
+  pub fn new<M>(kind: ContractKind, message: M) -> Self
+  where
+    M: fmt::Display,
+  {
+    Self::from_failure(kind, ContractFailure::new(message))
+  }
+
+  /// Wraps the details of a failure in the variant matching the given kind.
+  pub fn from_failure(kind: ContractKind, failure: ContractFailure) -> Self {
+    match kind {
+      ContractKind::Requires => RuntimeContractError::RequiresFailure(failure),
+      ContractKind::Ensures => RuntimeContractError::EnsuresFailure(failure),
+      ContractKind::Check => RuntimeContractError::CheckFailure(failure),
+    }
+  }
+
+  /// Creates an error for a contract of the given kind which could not be evaluated because its predicate failed.
+  pub fn unevaluated<E>(kind: ContractKind, failure: ContractFailure, source: E) -> Self
+  where
+    E: Into<Box<dyn Error + Send + Sync>>,
+  {
+    RuntimeContractError::EvaluationFailure {
+      kind,
+      failure: Box::new(failure),
+      source: PredicateError::new(source),
+    }
+  }
+
+  /// The kind of contract which was violated, or which could not be evaluated.
+  pub fn kind(&self) -> ContractKind {
+    match self {
+      RuntimeContractError::RequiresFailure(_) => ContractKind::Requires,
+      RuntimeContractError::EnsuresFailure(_) => ContractKind::Ensures,
+      RuntimeContractError::CheckFailure(_) => ContractKind::Check,
+      RuntimeContractError::EvaluationFailure { kind, .. } => *kind,
+    }
+  }
+
+  /// The details of the failure, regardless of its kind.
+  pub fn failure(&self) -> &ContractFailure {
+    match self {
+      RuntimeContractError::RequiresFailure(failure)
+      | RuntimeContractError::EnsuresFailure(failure)
+      | RuntimeContractError::CheckFailure(failure) => failure,
+      RuntimeContractError::EvaluationFailure { failure, .. } => failure,
+    }
+  }
+
+  /// Whether the contract was evaluated and found not to hold, as opposed to not being evaluated at all because its
+  /// predicate failed.
+  pub fn is_violation(&self) -> bool {
+    !matches!(self, RuntimeContractError::EvaluationFailure { .. })
+  }
+
+  /// The message given when the contract was declared.
+  pub fn message(&self) -> &str {
+    self.failure().message()
+  }
+
+  /// Where the violated contract was evaluated, if known.
+  pub fn location(&self) -> Option<&SourceLocation> {
+    self.failure().location()
+  }
+
+  /// The source text of the violated predicate, if it was recorded by one of the crate's macros.
+  pub fn predicate(&self) -> Option<&str> {
+    self.failure().predicate()
+  }
+
+  /// Which party is at fault for the violation: the caller for a precondition, the callee for a postcondition, and the
+  /// enclosing module for an invariant. See [`crate::blame`].
+  pub fn blame(&self) -> Blame {
+    let failure = self.failure();
+
+    Blame::new(
+      Party::for_kind(self.kind()),
+      failure.blame_label().map(str::to_string),
+      failure
+        .location()
+        .and_then(SourceLocation::module_path)
+        .map(str::to_string),
+    )
+  }
+}
+
+/// The error returned by a fallible predicate, which prevented its contract from being evaluated. It dereferences to the
+/// original error, which can be recovered with `downcast_ref`.
+#[derive(Clone)]
+pub struct PredicateError(Arc<dyn Error + Send + Sync>);
+
+impl PredicateError {
+  /// Wraps the error returned by a predicate. Besides any error type, this accepts errors which are already boxed, or which
+  /// convert into a box, such as `anyhow::Error`, as well as plain messages.
+  pub fn new<E>(error: E) -> Self
+  where
+    E: Into<Box<dyn Error + Send + Sync>>,
+  {
+    Self(Arc::from(error.into()))
+  }
+}
+
+// Not implementing `Error` itself lets `RuntimeContractError::source` yield the predicate's error rather than this wrapper.
+impl Deref for PredicateError {
+  type Target = dyn Error + Send + Sync + 'static;
+
+  fn deref(&self) -> &Self::Target {
+    &*self.0
+  }
+}
+
+impl fmt::Debug for PredicateError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::Debug::fmt(&self.0, f)
+  }
+}
+
+impl fmt::Display for PredicateError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::Display::fmt(&self.0, f)
+  }
+}
+
+/// Arbitrary errors cannot be compared, so predicate errors are equal when their messages are.
+impl PartialEq for PredicateError {
+  fn eq(&self, other: &Self) -> bool {
+    Arc::ptr_eq(&self.0, &other.0) || self.to_string() == other.to_string()
+  }
+}
+
+impl Eq for PredicateError {}
+
+/// The details of a contract failure: the message given when the contract was declared along with whatever could be
+/// recorded about where and how the contract was expressed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractFailure {
+  message: String,
+  // Boxed to keep results carrying this error small.
+  location: Option<Box<SourceLocation>>,
+  predicate: Option<String>,
+  context: Vec<(String, String)>,
+  blame_label: Option<String>,
+}
+
+impl ContractFailure {
+  /// Creates the details of a failure with only a message.
+  pub fn new<M>(message: M) -> Self
+  where
+    M: fmt::Display,
+  {
+    Self {
+      message: message.to_string(),
+      location: None,
+      predicate: None,
+      context: Vec::new(),
+      blame_label: None,
+    }
+  }
+
+  /// Records where the contract was evaluated.
+  pub fn with_location(mut self, location: SourceLocation) -> Self {
+    self.location = Some(Box::new(location));
+
+    self
+  }
+
+  /// Records the source text of the predicate.
+  pub fn with_predicate<P>(mut self, predicate: P) -> Self
+  where
+    P: fmt::Display,
+  {
+    self.predicate = Some(predicate.to_string());
+
+    self
+  }
+
+  /// Records an entry of the context in which the contract was evaluated.
+  pub fn with_context<K, V>(mut self, key: K, value: V) -> Self
+  where
+    K: fmt::Display,
+    V: fmt::Display,
+  {
+    self.context.push((key.to_string(), value.to_string()));
+
+    self
+  }
+
+  /// Records the label identifying the party at fault.
+  pub fn with_blame_label<L>(mut self, label: L) -> Self
+  where
+    L: fmt::Display,
+  {
+    self.blame_label = Some(label.to_string());
+
+    self
+  }
+
+  /// The message given when the contract was declared.
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+
+  /// Where the contract was evaluated, if known.
+  pub fn location(&self) -> Option<&SourceLocation> {
+    self.location.as_deref()
+  }
+
+  /// The source text of the predicate, if known.
+  pub fn predicate(&self) -> Option<&str> {
+    self.predicate.as_deref()
+  }
+
+  /// The context in scope when the contract was evaluated, from outermost to innermost. See [`crate::context`].
+  pub fn context(&self) -> &[(String, String)] {
+    &self.context
+  }
+
+  /// The label identifying the party at fault, if one is known.
+  pub fn blame_label(&self) -> Option<&str> {
+    self.blame_label.as_deref()
+  }
+}
+
+impl fmt::Display for ContractFailure {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(&self.message)?;
+
+    if let Some(predicate) = &self.predicate {
+      write!(f, " (`{predicate}`)")?;
+    }
+
+    if let Some(location) = &self.location {
+      write!(f, " at {location}")?;
+    }
+
+    Ok(())
+  }
+}
+
+/// A position in the source code where a contract was evaluated. The module path is only known for contracts expressed
+/// with the crate's macros.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SourceLocation {
+  file: Cow<'static, str>,
+  line: u32,
+  column: u32,
+  module_path: Option<Cow<'static, str>>,
+}
+
+impl SourceLocation {
+  /// Creates a location from its parts.
+  pub fn new<F>(file: F, line: u32, column: u32) -> Self
+  where
+    F: Into<Cow<'static, str>>,
+  {
+    Self {
+      file: file.into(),
+      line,
+      column,
+      module_path: None,
+    }
+  }
+
+  /// Records the path of the module containing the location.
+  pub fn with_module_path<P>(mut self, module_path: P) -> Self
+  where
+    P: Into<Cow<'static, str>>,
+  {
+    self.module_path = Some(module_path.into());
+
+    self
+  }
+
+  /// The source file.
+  pub fn file(&self) -> &str {
+    &self.file
+  }
+
+  /// The line within the source file, starting from 1.
+  pub fn line(&self) -> u32 {
+    self.line
+  }
+
+  /// The column within the line, starting from 1.
+  pub fn column(&self) -> u32 {
+    self.column
+  }
+
+  /// The path of the enclosing module, such as `my_crate::accounts`, if known.
+  pub fn module_path(&self) -> Option<&str> {
+    self.module_path.as_deref()
+  }
+}
+
+impl From<&'static std::panic::Location<'static>> for SourceLocation {
+  fn from(location: &'static std::panic::Location<'static>) -> Self {
+    Self::new(location.file(), location.line(), location.column())
+  }
+}
+
+impl fmt::Display for SourceLocation {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}:{}:{}", self.file, self.line, self.column)?;
+
+    if let Some(module_path) = &self.module_path {
+      write!(f, " in {module_path}")?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Every contract violated during a round of validation, as collected by a [`crate::set::ContractSet`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractViolations {
+  errors: Vec<RuntimeContractError>,
+}
+
+impl ContractViolations {
+  /// The number of violated contracts.
+  pub fn len(&self) -> usize {
+    self.errors.len()
+  }
+
+  /// Whether no contracts were violated. This is never the case for violations yielded by a [`crate::set::ContractSet`].
+  pub fn is_empty(&self) -> bool {
+    self.errors.is_empty()
+  }
+
+  /// Iterates over the violated contracts in the order they were evaluated.
+  pub fn iter(&self) -> std::slice::Iter<'_, RuntimeContractError> {
+    self.errors.iter()
+  }
+
+  /// Consumes the violations, yielding the underlying errors.
+  pub fn into_errors(self) -> Vec<RuntimeContractError> {
+    self.errors
+  }
+}
+
+impl fmt::Display for ContractViolations {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let plural = if self.errors.len() == 1 { "" } else { "s" };
+
+    write!(f, "{} contract violation{plural}", self.errors.len())?;
+
+    for (index, error) in self.errors.iter().enumerate() {
+      let separator = if index == 0 { ": " } else { "; " };
+
+      write!(f, "{separator}{error}")?;
+    }
+
+    Ok(())
+  }
+}
+
+impl std::error::Error for ContractViolations {}
+
+impl From<RuntimeContractError> for ContractViolations {
+  fn from(error: RuntimeContractError) -> Self {
+    Self {
+      errors: vec![error],
+    }
+  }
+}
+
+impl FromIterator<RuntimeContractError> for ContractViolations {
+  fn from_iter<I>(iter: I) -> Self
+  where
+    I: IntoIterator<Item = RuntimeContractError>,
+  {
+    Self {
+      errors: iter.into_iter().collect(),
+    }
+  }
+}
+
+impl IntoIterator for ContractViolations {
+  type Item = RuntimeContractError;
+  type IntoIter = std::vec::IntoIter<RuntimeContractError>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.errors.into_iter()
+  }
+}
+
+impl<'a> IntoIterator for &'a ContractViolations {
+  type Item = &'a RuntimeContractError;
+  type IntoIter = std::slice::Iter<'a, RuntimeContractError>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.errors.iter()
+  }
 }
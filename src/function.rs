@@ -0,0 +1,220 @@
+//! This module wraps functions in contracts, in the manner of Racket's `->` contracts. A wrapped function checks its
+//! argument before every call and its result after, so that a callback handed across a module boundary is guarded without
+//! touching any of its call sites.
+//!
+//! Blame follows the flow of values: an argument which violates its contract is the fault of whoever called the function,
+//! while a result which violates its contract is the fault of the function itself. The argument contract is therefore
+//! always checked as a precondition and the result contract as a postcondition, whatever kinds they were created with.
+//! Label the contracts with [`RuntimeContract::with_blame`] to name the parties involved.
+//!
+//! When a postcondition depends on the arguments, such as a result which must be as long as the input, describe the
+//! function with a [`DependentContract`] instead.
+use std::fmt;
+use std::sync::Arc;
+
+use crate::contract::{evaluate, ContractKind, Entry, RuntimeContract, Site};
+use crate::Result;
+
+/// Wraps a function so that every call checks its argument against `arg` and its result against `result`. Functions of
+/// several arguments can take them as a tuple. The function is not called when its argument is rejected.
+///
+/// When the argument and result types are the same, the wrapped function can be boxed as a
+/// [`RuntimeContractFunction`](crate::RuntimeContractFunction).
+///
+/// ```
+/// use runtime_contracts::blame::Party;
+/// use runtime_contracts::{contract_fn, RuntimeContract};
+///
+/// let halve = contract_fn(
+///   |i: u32| i / 2,
+///   RuntimeContract::requires(|i: &u32| i % 2 == 0, "argument must be even").with_blame("client"),
+///   RuntimeContract::ensures(|i: &u32| *i > 0, "result must be positive").with_blame("halver"),
+/// );
+///
+/// assert_eq!(halve(4), Ok(2));
+///
+/// let err = halve(3).unwrap_err();
+/// assert_eq!((err.blame().party(), err.blame().label()), (Party::Caller, Some("client")));
+///
+/// let err = halve(0).unwrap_err();
+/// assert_eq!((err.blame().party(), err.blame().label()), (Party::Callee, Some("halver")));
+/// ```
+#[track_caller]
+pub fn contract_fn<A, R, F>(
+  f: F,
+  arg: RuntimeContract<A>,
+  result: RuntimeContract<R>,
+) -> impl Fn(A) -> Result<R>
+where
+  F: Fn(A) -> R,
+{
+  // Closures cannot track their callers, so violations are reported where the function was wrapped.
+  let site = Site::caller();
+
+  move |value| {
+    arg.verify_as(ContractKind::Requires, site, &value)?;
+
+    let returned = f(value);
+
+    result.verify_as(ContractKind::Ensures, site, &returned)?;
+
+    Ok(returned)
+  }
+}
+
+/// The [context](crate::context) key under which a violation of a [`DependentContract`] names the dependency at fault.
+pub const DEPENDENCY: &str = "dependency";
+
+type Precondition<A> = dyn Fn(&A) -> bool + Send + Sync;
+type Postcondition<A, R> = dyn Fn(&A, &R) -> bool + Send + Sync;
+
+/// A named part of a [`DependentContract`].
+struct Dependency<P: ?Sized> {
+  name: &'static str,
+  message: String,
+  predicate: Arc<P>,
+}
+
+impl<P: ?Sized> Dependency<P> {
+  /// Evaluates the dependency as a contract of the given kind, recording its name so that a violation reports it.
+  fn evaluate<F>(&self, kind: ContractKind, site: Site, holds: F) -> Result<()>
+  where
+    F: FnOnce(&P) -> bool,
+  {
+    let site = site.with_entry(DEPENDENCY, Entry::Name(self.name));
+
+    evaluate(kind, site, || holds(&self.predicate), &self.message)
+  }
+}
+
+impl<P: ?Sized> Clone for Dependency<P> {
+  fn clone(&self) -> Self {
+    Self {
+      name: self.name,
+      message: self.message.clone(),
+      predicate: Arc::clone(&self.predicate),
+    }
+  }
+}
+
+/// A contract for a function whose postconditions depend on its arguments, in the manner of Racket's `->i` contracts. Each
+/// part of the contract is named, and a violation records the name of the part at fault in its
+/// [context](crate::error::ContractFailure::context) under the [`DEPENDENCY`] key.
+///
+/// Since the postconditions inspect the arguments once the function has returned, a wrapped function borrows its
+/// arguments. Functions of several arguments can borrow them as a tuple.
+///
+/// ```
+/// use runtime_contracts::function::{DependentContract, DEPENDENCY};
+///
+/// let double = DependentContract::new()
+///   .requires("bounded", |xs: &[u32]| xs.iter().all(|x| *x < 1000), "arguments must be below 1000")
+///   .ensures("same length", |xs: &[u32], ys: &Vec<u32>| xs.len() == ys.len(), "length must be preserved")
+///   .wrap(|xs: &[u32]| xs.iter().map(|x| x * 2).collect::<Vec<_>>());
+///
+/// assert_eq!(double(&[1, 2, 3]), Ok(vec![2, 4, 6]));
+///
+/// let err = double(&[1000]).unwrap_err();
+/// assert_eq!(err.failure().context(), &[(DEPENDENCY.to_string(), "bounded".to_string())]);
+/// ```
+pub struct DependentContract<A: ?Sized, R> {
+  requires: Vec<Dependency<Precondition<A>>>,
+  ensures: Vec<Dependency<Postcondition<A, R>>>,
+}
+
+impl<A: ?Sized, R> DependentContract<A, R> {
+  /// Creates a contract with no parts, which every function satisfies.
+  pub fn new() -> Self {
+    Self {
+      requires: Vec::new(),
+      ensures: Vec::new(),
+    }
+  }
+
+  /// Adds a named precondition on the arguments. Preconditions are checked in the order they were added.
+  pub fn requires<F, M>(mut self, name: &'static str, predicate: F, message: M) -> Self
+  where
+    F: Fn(&A) -> bool + Send + Sync + 'static,
+    M: fmt::Display,
+  {
+    self.requires.push(Dependency {
+      name,
+      message: message.to_string(),
+      predicate: Arc::new(predicate),
+    });
+
+    self
+  }
+
+  /// Adds a named postcondition relating the arguments to the result. Postconditions are checked in the order they were
+  /// added.
+  pub fn ensures<F, M>(mut self, name: &'static str, predicate: F, message: M) -> Self
+  where
+    F: Fn(&A, &R) -> bool + Send + Sync + 'static,
+    M: fmt::Display,
+  {
+    self.ensures.push(Dependency {
+      name,
+      message: message.to_string(),
+      predicate: Arc::new(predicate),
+    });
+
+    self
+  }
+
+  /// The names of the preconditions followed by those of the postconditions.
+  pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+    let requires = self.requires.iter().map(|dependency| dependency.name);
+    let ensures = self.ensures.iter().map(|dependency| dependency.name);
+
+    requires.chain(ensures)
+  }
+
+  /// Wraps a function so that every call checks its arguments against the preconditions and its result against the
+  /// postconditions. The function is not called when its arguments are rejected. As with [`contract_fn`], violations are
+  /// reported where the function was wrapped, and blame the caller for the arguments and the function for its result.
+  #[track_caller]
+  pub fn wrap<F>(self, f: F) -> impl Fn(&A) -> Result<R>
+  where
+    F: Fn(&A) -> R,
+  {
+    let site = Site::caller();
+
+    move |args: &A| {
+      for dependency in &self.requires {
+        dependency.evaluate(ContractKind::Requires, site, |holds| holds(args))?;
+      }
+
+      let returned = f(args);
+
+      for dependency in &self.ensures {
+        dependency.evaluate(ContractKind::Ensures, site, |holds| holds(args, &returned))?;
+      }
+
+      Ok(returned)
+    }
+  }
+}
+
+impl<A: ?Sized, R> Default for DependentContract<A, R> {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<A: ?Sized, R> Clone for DependentContract<A, R> {
+  fn clone(&self) -> Self {
+    Self {
+      requires: self.requires.clone(),
+      ensures: self.ensures.clone(),
+    }
+  }
+}
+
+impl<A: ?Sized, R> fmt::Debug for DependentContract<A, R> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("DependentContract")
+      .field("names", &self.names().collect::<Vec<_>>())
+      .finish()
+  }
+}
@@ -0,0 +1,102 @@
+//! This module contains a global registry of callbacks which are invoked on every contract violation, before the policy
+//! decides what the violation does. This allows violations to be reported even when the resulting error is swallowed
+//! further up the stack. Hooks receive the [`RuntimeContractError`] itself, whose accessors expose the kind of contract,
+//! its message, and where it was evaluated.
+//!
+//! Hooks may be registered from any thread and are invoked on the thread which evaluated the contract.
+//!
+//! ```
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//! use std::sync::Arc;
+//!
+//! use runtime_contracts::{hooks, requires};
+//!
+//! let violations = Arc::new(AtomicUsize::new(0));
+//! let counter = Arc::clone(&violations);
+//! let id = hooks::add_violation_hook(move |err| {
+//!   eprintln!("{} contract violated: {}", err.kind(), err.message());
+//!   counter.fetch_add(1, Ordering::Relaxed);
+//! });
+//!
+//! let _ = requires(|| false, "should always fail");
+//!
+//! assert_eq!(violations.load(Ordering::Relaxed), 1);
+//! assert!(hooks::remove_violation_hook(id));
+//! ```
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use crate::error::RuntimeContractError;
+
+/// A callback invoked on every contract violation.
+pub type ViolationHook = dyn Fn(&RuntimeContractError) + Send + Sync;
+
+/// Identifies a registered hook so that it can be removed later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HookId(u64);
+
+static HOOKS: RwLock<Vec<(HookId, Arc<ViolationHook>)>> = RwLock::new(Vec::new());
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Registers a hook alongside any existing ones. Hooks are invoked in the order they were added.
+pub fn add_violation_hook<F>(hook: F) -> HookId
+where
+  F: Fn(&RuntimeContractError) + Send + Sync + 'static,
+{
+  let id = HookId(NEXT_ID.fetch_add(1, Ordering::Relaxed));
+
+  write_hooks().push((id, Arc::new(hook)));
+
+  id
+}
+
+/// Replaces every registered hook with the given one.
+pub fn set_violation_hook<F>(hook: F) -> HookId
+where
+  F: Fn(&RuntimeContractError) + Send + Sync + 'static,
+{
+  let id = HookId(NEXT_ID.fetch_add(1, Ordering::Relaxed));
+  let mut hooks = write_hooks();
+
+  hooks.clear();
+  hooks.push((id, Arc::new(hook)));
+
+  id
+}
+
+/// Removes a previously registered hook, yielding whether it was still registered.
+pub fn remove_violation_hook(id: HookId) -> bool {
+  let mut hooks = write_hooks();
+  let before = hooks.len();
+
+  hooks.retain(|(hook_id, _)| *hook_id != id);
+
+  hooks.len() != before
+}
+
+/// Removes every registered hook.
+pub fn clear_violation_hooks() {
+  write_hooks().clear();
+}
+
+/// Invokes every registered hook with the given violation. The registry is not locked while hooks run, so hooks may
+/// themselves register or remove hooks.
+pub(crate) fn notify(error: &RuntimeContractError) {
+  let hooks: Vec<_> = HOOKS
+    .read()
+    .unwrap_or_else(|poisoned| poisoned.into_inner())
+    .iter()
+    .map(|(_, hook)| Arc::clone(hook))
+    .collect();
+
+  for hook in hooks {
+    hook(error);
+  }
+}
+
+fn write_hooks() -> std::sync::RwLockWriteGuard<'static, Vec<(HookId, Arc<ViolationHook>)>> {
+  HOOKS
+    .write()
+    .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
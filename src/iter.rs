@@ -0,0 +1,257 @@
+//! This module contains [`IteratorContracts`], an extension trait whose adapters check every element of an iterator as it
+//! is consumed, so that streamed records can be validated without writing a loop around [`crate::check()`]. Each adapter
+//! yields its elements as `Ok` values, or yields the error for the first violation and then stops. A violation records the
+//! position of the offending element in its [context](crate::error::ContractFailure::context) under the [`INDEX`] key.
+//!
+//! ```
+//! use runtime_contracts::iter::{IteratorContracts, INDEX};
+//!
+//! let readings = vec![3, 5, -1, 8];
+//! let err = readings
+//!   .into_iter()
+//!   .requires_each(|reading| *reading >= 0, "readings must not be negative")
+//!   .collect::<Result<Vec<_>, _>>()
+//!   .unwrap_err();
+//!
+//! assert_eq!(err.failure().context(), &[(INDEX.to_string(), "2".to_string())]);
+//! ```
+//!
+//! Like every other contract, the adapters are subject to the configured [`crate::policy`], so under a policy which only
+//! logs violations they carry on past them.
+use std::fmt;
+
+use crate::contract::{self, ContractKind, Entry, Site};
+use crate::Result;
+
+/// The [context](crate::context) key under which a violation names the index of the offending element.
+pub const INDEX: &str = "index";
+
+/// Adapters which check the elements of an iterator against contracts. It is implemented for every iterator.
+pub trait IteratorContracts: Iterator + Sized {
+  /// Checks every element against a precondition.
+  #[track_caller]
+  fn requires_each<F, M>(self, predicate: F, message: M) -> Each<Self, F>
+  where
+    F: FnMut(&Self::Item) -> bool,
+    M: fmt::Display,
+  {
+    Each::new(self, ContractKind::Requires, predicate, message)
+  }
+
+  /// Checks every element against a postcondition.
+  #[track_caller]
+  fn ensures_each<F, M>(self, predicate: F, message: M) -> Each<Self, F>
+  where
+    F: FnMut(&Self::Item) -> bool,
+    M: fmt::Display,
+  {
+    Each::new(self, ContractKind::Ensures, predicate, message)
+  }
+
+  /// Checks every element against an invariant.
+  #[track_caller]
+  fn check_each<F, M>(self, predicate: F, message: M) -> Each<Self, F>
+  where
+    F: FnMut(&Self::Item) -> bool,
+    M: fmt::Display,
+  {
+    Each::new(self, ContractKind::Check, predicate, message)
+  }
+
+  /// Checks that the elements are in ascending order. An element which is less than, or not comparable to, its predecessor
+  /// is a violation.
+  #[track_caller]
+  fn check_sorted(self) -> Sorted<Self>
+  where
+    Self::Item: PartialOrd + Clone,
+  {
+    Sorted {
+      iter: self,
+      previous: None,
+      position: Position::new(Site::caller()),
+    }
+  }
+
+  /// Checks that the iterator yields at least `min` and at most `max` elements. An iterator which is too long is stopped at
+  /// the first element past `max`, while one which is too short yields an error in place of its end, at the index one past
+  /// its last element.
+  #[track_caller]
+  fn check_len_between(self, min: usize, max: usize) -> LenBetween<Self> {
+    LenBetween {
+      iter: self,
+      min,
+      max,
+      message: format!("length must be between {min} and {max}"),
+      position: Position::new(Site::caller()),
+    }
+  }
+}
+
+impl<I: Iterator> IteratorContracts for I {}
+
+/// How far an adapter has got through its iterator, or through a stream.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Position {
+  site: Site,
+  pub(crate) index: usize,
+  pub(crate) stopped: bool,
+}
+
+impl Position {
+  pub(crate) fn new(site: Site) -> Self {
+    Self {
+      site,
+      index: 0,
+      stopped: false,
+    }
+  }
+
+  /// Evaluates a contract on the element at the current index and moves on to the next one, stopping after a violation.
+  pub(crate) fn verify<F, M>(&mut self, kind: ContractKind, pred: F, message: M) -> Result<()>
+  where
+    F: FnOnce() -> bool,
+    M: fmt::Display,
+  {
+    let site = self.site.with_entry(INDEX, Entry::Index(self.index));
+    let result = contract::evaluate(kind, site, pred, message);
+
+    self.index += 1;
+    self.stopped = result.is_err();
+
+    result
+  }
+}
+
+/// Checks every element of an iterator against a predicate. See [`IteratorContracts::requires_each`].
+#[must_use = "iterator adapters are lazy and do nothing unless consumed"]
+pub struct Each<I, F> {
+  iter: I,
+  kind: ContractKind,
+  predicate: F,
+  message: String,
+  position: Position,
+}
+
+impl<I, F> Each<I, F> {
+  #[track_caller]
+  fn new<M>(iter: I, kind: ContractKind, predicate: F, message: M) -> Self
+  where
+    M: fmt::Display,
+  {
+    Self {
+      iter,
+      kind,
+      predicate,
+      message: message.to_string(),
+      position: Position::new(Site::caller()),
+    }
+  }
+}
+
+impl<I, F> Iterator for Each<I, F>
+where
+  I: Iterator,
+  F: FnMut(&I::Item) -> bool,
+{
+  type Item = Result<I::Item>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.position.stopped {
+      return None;
+    }
+
+    let item = self.iter.next()?;
+    let predicate = &mut self.predicate;
+    let result = self
+      .position
+      .verify(self.kind, || predicate(&item), &self.message);
+
+    Some(result.map(|()| item))
+  }
+}
+
+impl<I: fmt::Debug, F> fmt::Debug for Each<I, F> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Each")
+      .field("iter", &self.iter)
+      .field("kind", &self.kind)
+      .field("message", &self.message)
+      .finish_non_exhaustive()
+  }
+}
+
+/// Checks that the elements of an iterator are in ascending order. See [`IteratorContracts::check_sorted`].
+#[must_use = "iterator adapters are lazy and do nothing unless consumed"]
+#[derive(Debug)]
+pub struct Sorted<I: Iterator> {
+  iter: I,
+  previous: Option<I::Item>,
+  position: Position,
+}
+
+impl<I> Iterator for Sorted<I>
+where
+  I: Iterator,
+  I::Item: PartialOrd + Clone,
+{
+  type Item = Result<I::Item>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.position.stopped {
+      return None;
+    }
+
+    let item = self.iter.next()?;
+    let previous = self.previous.replace(item.clone());
+    let result = self.position.verify(
+      ContractKind::Check,
+      || previous.is_none_or(|previous| previous <= item),
+      "elements must be sorted",
+    );
+
+    Some(result.map(|()| item))
+  }
+}
+
+/// Checks that an iterator yields a number of elements within bounds. See [`IteratorContracts::check_len_between`].
+#[must_use = "iterator adapters are lazy and do nothing unless consumed"]
+#[derive(Debug)]
+pub struct LenBetween<I> {
+  iter: I,
+  min: usize,
+  max: usize,
+  message: String,
+  position: Position,
+}
+
+impl<I: Iterator> Iterator for LenBetween<I> {
+  type Item = Result<I::Item>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.position.stopped {
+      return None;
+    }
+
+    let (index, max, min) = (self.position.index, self.max, self.min);
+
+    match self.iter.next() {
+      Some(item) => {
+        let result = self
+          .position
+          .verify(ContractKind::Check, || index < max, &self.message);
+
+        Some(result.map(|()| item))
+      }
+      None => {
+        let result = self
+          .position
+          .verify(ContractKind::Check, || index >= min, &self.message);
+
+        // The end is only checked once, even if the underlying iterator would carry on.
+        self.position.stopped = true;
+
+        result.err().map(Err)
+      }
+    }
+  }
+}
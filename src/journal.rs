@@ -0,0 +1,341 @@
+//! This module, available with the `journal` feature, persists violations to a local file so that they can be examined
+//! after the fact. Each violation is appended as a single JSON object on its own line (the [JSON Lines](https://jsonlines.org)
+//! format), recording when and where it happened, on which thread, and the [`crate::context`] in scope at the time. A
+//! contract whose predicate failed, so that it could not be evaluated, is recorded along with the `cause` of the failure.
+//!
+//! Writes are buffered, and the buffer is flushed when the journal is dropped. Once a file grows past a configured size it is
+//! rotated: `violations.jsonl` becomes `violations.jsonl.1`, the previous `violations.jsonl.1` becomes
+//! `violations.jsonl.2`, and so on, with the oldest file removed once the configured number of rotated files is reached.
+//!
+//! The simplest way to use a journal is to install it as a [violation hook](crate::hooks), which records every violation
+//! until the returned guard is dropped:
+//!
+//! ```no_run
+//! use runtime_contracts::journal::Journal;
+//!
+//! # fn main() -> std::io::Result<()> {
+//! let _journal = Journal::open("violations.jsonl")?
+//!   .with_max_size(10 * 1024 * 1024)
+//!   .install();
+//!
+//! // Every violation from here on is appended to `violations.jsonl`.
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! A record looks like this, though on a single line:
+//!
+//! ```json
+//! {
+//!   "timestamp": "2024-01-31T12:34:56.789Z",
+//!   "kind": "requires",
+//!   "message": "amount must be positive",
+//!   "predicate": "amount > 0",
+//!   "location": { "file": "src/accounts.rs", "line": 42, "column": 5, "module_path": "bank::accounts" },
+//!   "thread": "main",
+//!   "context": { "request_id": "7f3a" }
+//! }
+//! ```
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::contract::ContractKind;
+use crate::error::{RuntimeContractError, SourceLocation};
+use crate::hooks::{self, HookId};
+
+/// A single violation as written to a journal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+  timestamp: String,
+  kind: ContractKind,
+  message: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  predicate: Option<String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  location: Option<SourceLocation>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  thread: Option<String>,
+  #[serde(default)]
+  context: BTreeMap<String, String>,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  cause: Option<String>,
+}
+
+impl Record {
+  /// Describes a violation as having just happened on the current thread.
+  pub fn new(error: &RuntimeContractError) -> Self {
+    Self {
+      timestamp: timestamp(SystemTime::now()),
+      kind: error.kind(),
+      message: error.message().to_string(),
+      predicate: error.predicate().map(str::to_string),
+      location: error.location().cloned(),
+      thread: std::thread::current().name().map(str::to_string),
+      context: error.failure().context().iter().cloned().collect(),
+      cause: error.source().map(ToString::to_string),
+    }
+  }
+
+  /// When the violation happened, as an RFC 3339 timestamp in UTC with millisecond precision. Timestamps in this format
+  /// sort chronologically when compared as strings.
+  pub fn timestamp(&self) -> &str {
+    &self.timestamp
+  }
+
+  /// The kind of contract which was violated.
+  pub fn kind(&self) -> ContractKind {
+    self.kind
+  }
+
+  /// The message given when the contract was declared.
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+
+  /// The source text of the violated predicate, if known.
+  pub fn predicate(&self) -> Option<&str> {
+    self.predicate.as_deref()
+  }
+
+  /// Where the violated contract was evaluated, if known.
+  pub fn location(&self) -> Option<&SourceLocation> {
+    self.location.as_ref()
+  }
+
+  /// The name of the thread on which the violation happened, if it had one.
+  pub fn thread(&self) -> Option<&str> {
+    self.thread.as_deref()
+  }
+
+  /// The context in scope when the violation happened.
+  pub fn context(&self) -> &BTreeMap<String, String> {
+    &self.context
+  }
+
+  /// The message of the error which prevented the contract from being evaluated, if its predicate failed.
+  pub fn cause(&self) -> Option<&str> {
+    self.cause.as_deref()
+  }
+}
+
+/// Formats a point in time as an RFC 3339 timestamp in UTC, such as `2024-01-31T12:34:56.789Z`.
+fn timestamp(time: SystemTime) -> String {
+  let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+  let seconds = since_epoch.as_secs();
+  let (year, month, day) = civil_from_days(seconds / 86_400);
+  let seconds_of_day = seconds % 86_400;
+
+  format!(
+    "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+    seconds_of_day / 3_600,
+    seconds_of_day % 3_600 / 60,
+    seconds_of_day % 60,
+    since_epoch.subsec_millis(),
+  )
+}
+
+/// Converts a number of days since the Unix epoch into a year, month, and day, following Howard Hinnant's algorithm.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+  let days = days + 719_468;
+  let era = days / 146_097;
+  let day_of_era = days % 146_097;
+  let year_of_era =
+    (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+  let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+  let shifted_month = (5 * day_of_year + 2) / 153;
+  let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+  let month = if shifted_month < 10 {
+    shifted_month + 3
+  } else {
+    shifted_month - 9
+  };
+  let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+  (year, month, day)
+}
+
+/// The open file of a journal along with how much has been written to it.
+struct Output {
+  writer: BufWriter<File>,
+  size: u64,
+}
+
+/// An append-only JSON Lines file of violations. A journal can be shared between threads, and its buffered records are
+/// flushed when it is dropped.
+pub struct Journal {
+  path: PathBuf,
+  max_size: Option<u64>,
+  max_files: usize,
+  output: Mutex<Output>,
+}
+
+impl Journal {
+  /// Opens a journal at the given path, appending to the file if it already exists. By default the file is never rotated.
+  pub fn open<P>(path: P) -> io::Result<Self>
+  where
+    P: AsRef<Path>,
+  {
+    let path = path.as_ref().to_path_buf();
+    let output = Self::open_output(&path)?;
+
+    Ok(Self {
+      path,
+      max_size: None,
+      max_files: 5,
+      output: Mutex::new(output),
+    })
+  }
+
+  /// Rotates the file once writing a record would grow it past the given number of bytes.
+  pub fn with_max_size(mut self, bytes: u64) -> Self {
+    self.max_size = Some(bytes);
+
+    self
+  }
+
+  /// Keeps at most the given number of rotated files, removing the oldest beyond that. The default is 5.
+  pub fn with_max_files(mut self, count: usize) -> Self {
+    self.max_files = count;
+
+    self
+  }
+
+  /// The path of the file currently being written.
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  /// Appends a violation to the journal.
+  pub fn record(&self, error: &RuntimeContractError) -> io::Result<()> {
+    self.write(&Record::new(error))
+  }
+
+  /// Appends an existing record to the journal.
+  pub fn write(&self, record: &Record) -> io::Result<()> {
+    let mut line = serde_json::to_vec(record)?;
+    line.push(b'\n');
+
+    let mut output = self.lock();
+    let len = line.len() as u64;
+
+    if let Some(max_size) = self.max_size {
+      if output.size > 0 && output.size + len > max_size {
+        self.rotate(&mut output)?;
+      }
+    }
+
+    output.writer.write_all(&line)?;
+    output.size += len;
+
+    Ok(())
+  }
+
+  /// Writes any buffered records to the file.
+  pub fn flush(&self) -> io::Result<()> {
+    self.lock().writer.flush()
+  }
+
+  /// Registers the journal as a violation hook, so that every violation is recorded until the returned guard is dropped.
+  /// Failures to write are reported on standard error.
+  pub fn install(self) -> JournalGuard {
+    let journal = Arc::new(self);
+    let hook_journal = Arc::clone(&journal);
+
+    let id = hooks::add_violation_hook(move |error| {
+      if let Err(err) = hook_journal.record(error) {
+        eprintln!(
+          "failed to write to violation journal {}: {err}",
+          hook_journal.path.display()
+        );
+      }
+    });
+
+    JournalGuard { id, journal }
+  }
+
+  fn lock(&self) -> std::sync::MutexGuard<'_, Output> {
+    self
+      .output
+      .lock()
+      .unwrap_or_else(|poisoned| poisoned.into_inner())
+  }
+
+  fn open_output(path: &Path) -> io::Result<Output> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let size = file.metadata()?.len();
+
+    Ok(Output {
+      writer: BufWriter::new(file),
+      size,
+    })
+  }
+
+  fn rotated_path(&self, index: usize) -> PathBuf {
+    let mut path = self.path.clone().into_os_string();
+    path.push(format!(".{index}"));
+
+    PathBuf::from(path)
+  }
+
+  fn rotate(&self, output: &mut Output) -> io::Result<()> {
+    output.writer.flush()?;
+
+    if self.max_files == 0 {
+      fs::remove_file(&self.path)?;
+    } else {
+      match fs::remove_file(self.rotated_path(self.max_files)) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+        _ => {}
+      }
+
+      for index in (1..self.max_files).rev() {
+        match fs::rename(self.rotated_path(index), self.rotated_path(index + 1)) {
+          Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err),
+          _ => {}
+        }
+      }
+
+      fs::rename(&self.path, self.rotated_path(1))?;
+    }
+
+    *output = Self::open_output(&self.path)?;
+
+    Ok(())
+  }
+}
+
+impl Drop for Journal {
+  fn drop(&mut self) {
+    // Errors cannot be reported from here, and the records are lost either way.
+    let _ = self.flush();
+  }
+}
+
+/// Keeps a [`Journal`] registered as a violation hook. Dropping the guard removes the hook and flushes the journal.
+#[must_use = "the journal stops recording violations as soon as the guard is dropped"]
+pub struct JournalGuard {
+  id: HookId,
+  journal: Arc<Journal>,
+}
+
+impl JournalGuard {
+  /// The installed journal, for flushing it early.
+  pub fn journal(&self) -> &Journal {
+    &self.journal
+  }
+}
+
+impl Drop for JournalGuard {
+  fn drop(&mut self) {
+    hooks::remove_violation_hook(self.id);
+
+    let _ = self.journal.flush();
+  }
+}
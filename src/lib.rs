@@ -10,12 +10,34 @@
 //! concept expressed by noted Computer Scientist, [Dr. Betrand Meyer](https://en.wikipedia.org/wiki/Bertrand_Meyer) when
 //! designing the [Eiffel programming language](https://en.wikipedia.org/wiki/Eiffel_(programming_language)) in 1986.
 //!
+//! What a violated contract does is configurable at runtime through the [`policy`] module: it can return an error (the
+//! default), panic, log and carry on, or skip evaluation entirely. Eiffel-style assertion levels, also in that module,
+//! select which kinds of contract are evaluated at all, while the [`cost`] module can sample or switch off contracts which are
+//! too expensive to evaluate on every call. To be alerted of violations wherever they happen, register a callback with the
+//! [`hooks`] module, and to find out which contracts actually fire, take a [`stats()`] snapshot of the per-site counters
+//! kept by the [`stats`](mod@stats) module, which the [`prometheus`] module can render for scraping. With the `tracing`
+//! feature enabled, the `trace` module emits an event for every evaluation and every violation, while the `log` feature reports violations
+//! through the `log` crate as described in the `logging` module. For post-mortems, the `journal` feature appends every
+//! violation to a JSON Lines file, along with the [`context`] in which it happened, while the `serde` feature makes the
+//! errors themselves serializable with the schema documented in the `serialization` module. HTTP services can enable the
+//! `problem` feature to turn violations into RFC 7807 problem details.
+//!
+//! If you would rather declare contracts alongside a function's signature, enable the `macros` feature for the attribute
+//! macros in the `macros` module.
+//!
+//! In the spirit of Racket, every violation assigns [`blame`] to the party at fault, and [`contract_fn`] wraps a function
+//! so that its argument and result are checked on every call. To check every element of an iterator as it is consumed,
+//! use the adapters in the [`iter`] module. The `async` feature adds contracts whose predicates are futures, along with
+//! adapters for futures and streams, in the `asynchronous` module.
+//!
 //! Additionally, much thanks goes to the [`contracts`](https://crates.io/crates/contracts) crate which implements contacts
 //! as procedural macros. Definitely check it out!
 //!
 //! # Examples
 //!
-//! Though this example uses the crate's own error type, you can substitute whatever you wish so long as it works.
+//! Though this example uses the crate's own error type, you can substitute whatever you wish so long as it works. The
+//! [`requires_or`], [`ensures_or`], and [`check_or`] variants produce your own error type directly, while the
+//! [`try_requires`], [`try_ensures`], and [`try_check`] variants accept predicates which can fail on their own.
 //!
 //! ```
 //! use runtime_contracts::{check, ensures, requires, error::RuntimeContractError, Result};
@@ -44,10 +66,43 @@
 //! }
 //! ```
 
+#[cfg(feature = "async")]
+pub mod asynchronous;
+pub mod blame;
+pub mod context;
+pub mod contract;
+pub mod cost;
 pub mod error;
+pub mod function;
+pub mod hooks;
+pub mod iter;
+#[cfg(feature = "journal")]
+pub mod journal;
+#[cfg(feature = "log")]
+pub mod logging;
+#[cfg(feature = "macros")]
+pub mod macros;
+pub mod policy;
+#[cfg(feature = "problem")]
+pub mod problem;
+pub mod prometheus;
+#[cfg(feature = "serde")]
+pub mod serialization;
+pub mod set;
+pub mod stats;
+#[cfg(feature = "tracing")]
+pub mod trace;
+
+pub use contract::{ContractKind, RuntimeContract};
+pub use function::contract_fn;
+pub use stats::snapshot as stats;
+
+use contract::Site;
 
 pub type Result<T, E = error::RuntimeContractError> = core::result::Result<T, E>;
 
+/// A contract in function form, which yields its argument if the contract holds. See [`RuntimeContract::into_fn`] and
+/// [`contract_fn`].
 pub type RuntimeContractFunction<T> = dyn Fn(T) -> Result<T>;
 
 /// Checks an arbitrary condition expressed by the given predicate. This is most useful for validating arguments at the _start_
@@ -103,18 +158,13 @@ pub type RuntimeContractFunction<T> = dyn Fn(T) -> Result<T>;
 ///
 /// assert!(add_two(2, 3).is_ok());
 /// ```
+#[track_caller]
 pub fn requires<F, M>(pred: F, message: M) -> Result<()>
 where
   F: Fn() -> bool,
   M: std::fmt::Display,
 {
-  if pred() {
-    Ok(())
-  } else {
-    let err = error::RuntimeContractError::RequiresFailure(message.to_string());
-
-    Err(err)
-  }
+  contract::evaluate(ContractKind::Requires, Site::caller(), pred, message)
 }
 
 /// Checks an arbitrary condition expressed in a predicate run against a given value. If the condition is satisfied(read: if the
@@ -144,35 +194,297 @@ where
 /// assert!(add_two(5, -5).is_err());
 /// ```
 ///
+#[track_caller]
 pub fn ensures<T, F, M>(value: T, predicate: F, message: M) -> Result<T>
 where
   T: Clone,
   F: FnOnce(&T) -> bool,
   M: std::fmt::Display,
 {
-  if predicate(&value) {
-    Ok(value)
-  } else {
-    let err = error::RuntimeContractError::EnsuresFailure(message.to_string());
-
-    Err(err)
-  }
+  __private::ensures(Site::caller(), value, predicate, message)
 }
 
 /// Verifies than an arbitrary condition is met, intended to verify preservation of an invariant at runtime.
 /// Think of this as a `requires` designed to be used anywhere in control flow.
-
+#[track_caller]
 pub fn check<F, M>(pred: F, message: M) -> Result<()>
 where
   F: FnOnce() -> bool,
   M: std::fmt::Display,
 {
-  if pred() {
-    Ok(())
-  } else {
-    let err_msg = format!("invariant violated: {message}",);
-    let err = error::RuntimeContractError::CheckFailure(err_msg);
+  contract::evaluate(ContractKind::Check, Site::caller(), pred, message)
+}
+
+/// Like [`requires()`], but produces a caller-defined error instead of a [`error::RuntimeContractError`]. This saves mapping
+/// errors at every call site when a crate has its own error type. The error is only built if the predicate does not hold,
+/// and its `Display` output is used as the contract's message.
+///
+/// ```
+/// use runtime_contracts::requires_or;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum AccountError {
+///   MalformedId,
+/// }
+///
+/// impl std::fmt::Display for AccountError {
+///   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///     f.write_str("malformed account ID")
+///   }
+/// }
+///
+/// fn load_account(account_id: &str) -> Result<usize, AccountError> {
+///   requires_or(|| account_id.len() == 32, || AccountError::MalformedId)?;
+///
+///   Ok(613)
+/// }
+///
+/// assert_eq!(load_account("1234"), Err(AccountError::MalformedId));
+/// ```
+#[track_caller]
+pub fn requires_or<F, G, E>(pred: F, err: G) -> Result<(), E>
+where
+  F: FnOnce() -> bool,
+  G: FnOnce() -> E,
+  E: std::fmt::Display,
+{
+  contract::evaluate_or(ContractKind::Requires, Site::caller(), pred, err)
+}
+
+/// Like [`ensures()`], but produces a caller-defined error instead of a [`error::RuntimeContractError`]. See [`requires_or`].
+#[track_caller]
+pub fn ensures_or<T, F, G, E>(value: T, predicate: F, err: G) -> Result<T, E>
+where
+  F: FnOnce(&T) -> bool,
+  G: FnOnce() -> E,
+  E: std::fmt::Display,
+{
+  contract::evaluate_or(
+    ContractKind::Ensures,
+    Site::caller(),
+    || predicate(&value),
+    err,
+  )?;
+
+  Ok(value)
+}
+
+/// Like [`check()`], but produces a caller-defined error instead of a [`error::RuntimeContractError`]. See [`requires_or`].
+#[track_caller]
+pub fn check_or<F, G, E>(pred: F, err: G) -> Result<(), E>
+where
+  F: FnOnce() -> bool,
+  G: FnOnce() -> E,
+  E: std::fmt::Display,
+{
+  contract::evaluate_or(ContractKind::Check, Site::caller(), pred, err)
+}
+
+/// Like [`requires()`], but for predicates which can fail on their own, such as those parsing input or doing I/O. A predicate
+/// returning an error means the contract could not be evaluated, which yields an
+/// [`EvaluationFailure`](error::RuntimeContractError::EvaluationFailure) whose [`source`](std::error::Error::source) is the
+/// predicate's error, rather than a violation. The error can be of any type, or already boxed, so predicates can use `?` on
+/// errors of several types by returning a `Box<dyn Error + Send + Sync>`.
+///
+/// ```
+/// use std::error::Error;
+/// use std::path::Path;
+///
+/// use runtime_contracts::try_requires;
+///
+/// let config = Path::new("/no/such/config.toml");
+/// let err = try_requires(|| config.metadata().map(|metadata| metadata.is_file()), "config must be a file").unwrap_err();
+///
+/// assert!(!err.is_violation());
+/// assert!(err.source().unwrap().downcast_ref::<std::io::Error>().is_some());
+/// ```
+#[track_caller]
+pub fn try_requires<F, E, M>(pred: F, message: M) -> Result<()>
+where
+  F: FnOnce() -> core::result::Result<bool, E>,
+  E: Into<Box<dyn std::error::Error + Send + Sync>>,
+  M: std::fmt::Display,
+{
+  contract::try_evaluate(ContractKind::Requires, Site::caller(), pred, message)
+}
+
+/// Like [`ensures()`], but for predicates which can fail on their own. See [`try_requires`].
+#[track_caller]
+pub fn try_ensures<T, F, E, M>(value: T, predicate: F, message: M) -> Result<T>
+where
+  F: FnOnce(&T) -> core::result::Result<bool, E>,
+  E: Into<Box<dyn std::error::Error + Send + Sync>>,
+  M: std::fmt::Display,
+{
+  contract::try_evaluate(
+    ContractKind::Ensures,
+    Site::caller(),
+    || predicate(&value),
+    message,
+  )?;
+
+  Ok(value)
+}
+
+/// Like [`check()`], but for predicates which can fail on their own. See [`try_requires`].
+#[track_caller]
+pub fn try_check<F, E, M>(pred: F, message: M) -> Result<()>
+where
+  F: FnOnce() -> core::result::Result<bool, E>,
+  E: Into<Box<dyn std::error::Error + Send + Sync>>,
+  M: std::fmt::Display,
+{
+  contract::try_evaluate(ContractKind::Check, Site::caller(), pred, message)
+}
+
+/// Like [`requires()`], but takes the condition as an expression in the manner of `assert!`. Failures additionally record the
+/// source text of the condition and the enclosing module. The message is optional and defaults to the condition itself.
+///
+/// ```
+/// use runtime_contracts::{requires, Result};
+///
+/// fn add_two(i: i32, j: i32) -> Result<i32> {
+///   requires!(i > 0, "i must be greater than 0")?;
+///   requires!(j > 0)?;
+///
+///   Ok(i + j)
+/// }
+///
+/// let err = add_two(2, -3).unwrap_err();
+///
+/// assert_eq!(err.predicate(), Some("j > 0"));
+/// assert_eq!(err.location().and_then(|location| location.module_path()), Some(module_path!()));
+/// ```
+///
+/// A contract which is costly to evaluate can be tagged with its [`cost::Cost`] class as a last argument, so that its
+/// evaluations are subject to the class's [`cost::Gate`]. The same argument is accepted by [`ensures!`] and [`check!`].
+///
+/// ```
+/// use runtime_contracts::cost::{self, Cost, Gate};
+/// use runtime_contracts::requires;
+///
+/// let ids = vec![3, 1, 2];
+///
+/// cost::set_gate(Cost::Expensive, Gate::Never);
+/// assert!(requires!(ids.windows(2).all(|pair| pair[0] <= pair[1]), cost = Cost::Expensive).is_ok());
+/// cost::set_gate(Cost::Expensive, Gate::Always);
+/// ```
+#[macro_export]
+macro_rules! requires {
+  ($pred:expr, cost = $cost:expr $(,)?) => {
+    $crate::requires!($pred, ::core::stringify!($pred), cost = $cost)
+  };
+  ($pred:expr, $message:expr, cost = $cost:expr $(,)?) => {
+    $crate::__private::requires($crate::__site!($pred).with_cost($cost), || $pred, $message)
+  };
+  ($pred:expr $(,)?) => {
+    $crate::requires!($pred, ::core::stringify!($pred))
+  };
+  ($pred:expr, $message:expr $(,)?) => {
+    $crate::__private::requires($crate::__site!($pred), || $pred, $message)
+  };
+}
+
+/// Like [`ensures()`], recording the source text of the predicate and the enclosing module in case of failure. The message
+/// is optional and defaults to the predicate itself.
+///
+/// ```
+/// use runtime_contracts::{ensures, Result};
+///
+/// fn add_two(i: i32, j: i32) -> Result<i32> {
+///   ensures!(i + j, |sum| *sum > 0, "the sum of i and j must be greater than 0")
+/// }
+///
+/// assert_eq!(add_two(5, 6), Ok(11));
+/// assert!(add_two(5, -5).is_err());
+/// ```
+#[macro_export]
+macro_rules! ensures {
+  ($value:expr, $pred:expr, cost = $cost:expr $(,)?) => {
+    $crate::ensures!($value, $pred, ::core::stringify!($pred), cost = $cost)
+  };
+  ($value:expr, $pred:expr, $message:expr, cost = $cost:expr $(,)?) => {
+    $crate::__private::ensures(
+      $crate::__site!($pred).with_cost($cost),
+      $value,
+      $pred,
+      $message,
+    )
+  };
+  ($value:expr, $pred:expr $(,)?) => {
+    $crate::ensures!($value, $pred, ::core::stringify!($pred))
+  };
+  ($value:expr, $pred:expr, $message:expr $(,)?) => {
+    $crate::__private::ensures($crate::__site!($pred), $value, $pred, $message)
+  };
+}
+
+/// Like [`check()`], but takes the condition as an expression in the manner of `assert!`. Failures additionally record the
+/// source text of the condition and the enclosing module. The message is optional and defaults to the condition itself.
+///
+/// ```
+/// use runtime_contracts::check;
+///
+/// let balance = -5;
+/// let err = check!(balance >= 0, "balance must never be negative").unwrap_err();
+///
+/// assert_eq!(err.predicate(), Some("balance >= 0"));
+/// ```
+#[macro_export]
+macro_rules! check {
+  ($pred:expr, cost = $cost:expr $(,)?) => {
+    $crate::check!($pred, ::core::stringify!($pred), cost = $cost)
+  };
+  ($pred:expr, $message:expr, cost = $cost:expr $(,)?) => {
+    $crate::__private::check($crate::__site!($pred).with_cost($cost), || $pred, $message)
+  };
+  ($pred:expr $(,)?) => {
+    $crate::check!($pred, ::core::stringify!($pred))
+  };
+  ($pred:expr, $message:expr $(,)?) => {
+    $crate::__private::check($crate::__site!($pred), || $pred, $message)
+  };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __site {
+  ($pred:expr) => {
+    $crate::contract::Site::caller()
+      .in_module(::core::module_path!())
+      .with_predicate(::core::stringify!($pred))
+  };
+}
+
+/// Entry points used by the crate's macros. These are not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+  use crate::contract::{self, ContractKind, Site};
+  use crate::Result;
+
+  pub fn requires<F, M>(site: Site, pred: F, message: M) -> Result<()>
+  where
+    F: FnOnce() -> bool,
+    M: std::fmt::Display,
+  {
+    contract::evaluate(ContractKind::Requires, site, pred, message)
+  }
+
+  pub fn ensures<T, F, M>(site: Site, value: T, predicate: F, message: M) -> Result<T>
+  where
+    F: FnOnce(&T) -> bool,
+    M: std::fmt::Display,
+  {
+    contract::evaluate(ContractKind::Ensures, site, || predicate(&value), message)?;
+
+    Ok(value)
+  }
 
-    Err(err)
+  pub fn check<F, M>(site: Site, pred: F, message: M) -> Result<()>
+  where
+    F: FnOnce() -> bool,
+    M: std::fmt::Display,
+  {
+    contract::evaluate(ContractKind::Check, site, pred, message)
   }
 }
@@ -0,0 +1,30 @@
+//! This module reports violations through the [`log`](https://docs.rs/log) crate. It is only available with the `log`
+//! feature enabled.
+//!
+//! Each violation is logged with a target naming its kind, such as `runtime_contracts::requires`, so that it can be
+//! filtered with the likes of `env_logger`. Violations which the [`Policy`] lets through are logged at the `warn` level,
+//! while all others are logged at the `error` level. With the `log` feature enabled, [`Policy::Log`] relies on this
+//! module instead of writing to standard error.
+use log::Level;
+
+use crate::contract::ContractKind;
+use crate::error::RuntimeContractError;
+use crate::policy::Policy;
+
+/// The `log` target used for violations of contracts of the given kind.
+pub fn target(kind: ContractKind) -> &'static str {
+  match kind {
+    ContractKind::Requires => "runtime_contracts::requires",
+    ContractKind::Ensures => "runtime_contracts::ensures",
+    ContractKind::Check => "runtime_contracts::check",
+  }
+}
+
+pub(crate) fn violated(error: &RuntimeContractError, policy: Policy) {
+  let level = match policy {
+    Policy::Log => Level::Warn,
+    Policy::Error | Policy::Panic | Policy::Disabled => Level::Error,
+  };
+
+  log::log!(target: target(error.kind()), level, "{error}");
+}
@@ -0,0 +1,47 @@
+//! This module re-exports the attribute macros from the companion `runtime-contracts-macros` crate. It is only available
+//! with the `macros` feature enabled.
+//!
+//! The macros expand to calls to [`requires`](crate::requires()), [`ensures`](crate::ensures()), and [`check`](crate::check()),
+//! so they can only be applied to functions returning a `Result` whose error type implements
+//! `From<RuntimeContractError>`. Within an `ensures` condition, `ret` refers to the successful return value and `old(expr)`
+//! refers to a clone of `expr` taken before the function body ran.
+//!
+//! ```
+//! use runtime_contracts::macros::{ensures, requires};
+//! use runtime_contracts::Result;
+//!
+//! #[requires(i > 0, "i must be greater than 0")]
+//! #[requires(j > 0, "j must be greater than 0")]
+//! #[ensures(*ret > i && *ret > j, "the sum must exceed both addends")]
+//! fn add_two(i: i32, j: i32) -> Result<i32> {
+//!   Ok(i + j)
+//! }
+//!
+//! assert_eq!(add_two(2, 3), Ok(5));
+//! assert!(add_two(-2, 3).is_err());
+//! ```
+//!
+//! Snapshots taken with `old` make it easy to relate a function's outputs to its inputs:
+//!
+//! ```
+//! use runtime_contracts::macros::ensures;
+//! use runtime_contracts::Result;
+//!
+//! struct Account {
+//!   balance: usize,
+//! }
+//!
+//! impl Account {
+//!   #[ensures(self.balance - old(self.balance) == amount, "points were not added to account")]
+//!   fn add_to_balance(&mut self, amount: usize) -> Result<usize> {
+//!     self.balance += amount;
+//!
+//!     Ok(self.balance)
+//!   }
+//! }
+//!
+//! let mut account = Account { balance: 613 };
+//!
+//! assert_eq!(account.add_to_balance(2), Ok(615));
+//! ```
+pub use runtime_contracts_macros::{ensures, invariant, requires};
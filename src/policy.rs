@@ -0,0 +1,298 @@
+//! This module controls what happens when a contract is violated. By default, violations are returned as errors, which
+//! preserves the crate's original behavior. The policy can be changed globally or for each [`ContractKind`], either
+//! programmatically or through the `RUNTIME_CONTRACTS_POLICY` environment variable.
+//!
+//! The environment variable holds a comma-separated list of policies. A bare policy applies to every kind, while an entry
+//! such as `ensures=log` applies to a single kind. Later entries take precedence, so `log,requires=error` logs violated
+//! postconditions and invariants while still returning errors for preconditions.
+//!
+//! Independently of the policy, an Eiffel-style [`AssertionLevel`] selects which kinds of contract are evaluated at all.
+//! Levels are cumulative, so `ensures` evaluates preconditions and postconditions but skips invariants. The level can be set
+//! programmatically or through the `RUNTIME_CONTRACTS_LEVEL` environment variable.
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Once;
+
+use crate::contract::ContractKind;
+use crate::error::RuntimeContractError;
+use crate::Result;
+
+/// The name of the environment variable read when a policy is first needed.
+pub const POLICY_ENV_VAR: &str = "RUNTIME_CONTRACTS_POLICY";
+
+/// The name of the environment variable read when the assertion level is first needed.
+pub const LEVEL_ENV_VAR: &str = "RUNTIME_CONTRACTS_LEVEL";
+
+/// What to do when a contract is violated, or whether to evaluate it at all.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Policy {
+  /// Return the violation as an error. This is the default.
+  #[default]
+  Error,
+  /// Panic with the violation as the message.
+  Panic,
+  /// Report the violation and carry on as if the contract held. Violations are written to standard error, or reported
+  /// through the `log` crate when the `log` feature is enabled.
+  Log,
+  /// Skip evaluating the predicate entirely.
+  Disabled,
+}
+
+impl Policy {
+  const ALL: [Policy; 4] = [Policy::Error, Policy::Panic, Policy::Log, Policy::Disabled];
+
+  fn from_u8(value: u8) -> Self {
+    Self::ALL[usize::from(value)]
+  }
+
+  fn as_u8(self) -> u8 {
+    self as u8
+  }
+
+  /// Applies this policy to a violation, yielding what the contract should return.
+  pub(crate) fn enforce(self, error: RuntimeContractError) -> Result<()> {
+    match self {
+      Policy::Error => Err(error),
+      Policy::Panic => panic!("{error}"),
+      Policy::Log => {
+        // With the `log` feature enabled, the violation has already been reported through the `log` crate.
+        #[cfg(not(feature = "log"))]
+        eprintln!("{error}");
+
+        Ok(())
+      }
+      Policy::Disabled => Ok(()),
+    }
+  }
+}
+
+impl fmt::Display for Policy {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let name = match self {
+      Policy::Error => "error",
+      Policy::Panic => "panic",
+      Policy::Log => "log",
+      Policy::Disabled => "disabled",
+    };
+
+    f.write_str(name)
+  }
+}
+
+/// Which kinds of contract are evaluated, in the manner of Eiffel's assertion monitoring levels. Each level includes every
+/// level below it, and contracts of excluded kinds are skipped as if their policy were [`Policy::Disabled`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AssertionLevel {
+  /// Evaluate no contracts.
+  None,
+  /// Evaluate preconditions only.
+  Requires,
+  /// Evaluate preconditions and postconditions.
+  Ensures,
+  /// Evaluate preconditions, postconditions, and invariants.
+  Check,
+  /// Evaluate every contract. This is the default.
+  #[default]
+  All,
+}
+
+impl AssertionLevel {
+  const ALL: [AssertionLevel; 5] = [
+    AssertionLevel::None,
+    AssertionLevel::Requires,
+    AssertionLevel::Ensures,
+    AssertionLevel::Check,
+    AssertionLevel::All,
+  ];
+
+  /// Whether contracts of the given kind are evaluated at this level.
+  pub fn enables(self, kind: ContractKind) -> bool {
+    let required = match kind {
+      ContractKind::Requires => AssertionLevel::Requires,
+      ContractKind::Ensures => AssertionLevel::Ensures,
+      ContractKind::Check => AssertionLevel::Check,
+    };
+
+    self >= required
+  }
+}
+
+impl fmt::Display for AssertionLevel {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let name = match self {
+      AssertionLevel::None => "none",
+      AssertionLevel::Requires => "requires",
+      AssertionLevel::Ensures => "ensures",
+      AssertionLevel::Check => "check",
+      AssertionLevel::All => "all",
+    };
+
+    f.write_str(name)
+  }
+}
+
+impl FromStr for AssertionLevel {
+  type Err = ParseError;
+
+  fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+    match s.trim().to_ascii_lowercase().as_str() {
+      "none" => Ok(AssertionLevel::None),
+      "requires" => Ok(AssertionLevel::Requires),
+      "ensures" => Ok(AssertionLevel::Ensures),
+      "check" => Ok(AssertionLevel::Check),
+      "all" => Ok(AssertionLevel::All),
+      _ => Err(ParseError {
+        what: "assertion level",
+        value: s.to_string(),
+      }),
+    }
+  }
+}
+
+/// The error returned when parsing a [`Policy`], an [`AssertionLevel`], or a [`ContractKind`] from an unrecognized string.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("unrecognized {what}: {value:?}")]
+pub struct ParseError {
+  what: &'static str,
+  value: String,
+}
+
+impl FromStr for Policy {
+  type Err = ParseError;
+
+  fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+    match s.trim().to_ascii_lowercase().as_str() {
+      "error" => Ok(Policy::Error),
+      "panic" => Ok(Policy::Panic),
+      "log" => Ok(Policy::Log),
+      "disabled" | "off" => Ok(Policy::Disabled),
+      _ => Err(ParseError {
+        what: "policy",
+        value: s.to_string(),
+      }),
+    }
+  }
+}
+
+impl FromStr for ContractKind {
+  type Err = ParseError;
+
+  fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+    match s.trim().to_ascii_lowercase().as_str() {
+      "requires" => Ok(ContractKind::Requires),
+      "ensures" => Ok(ContractKind::Ensures),
+      "check" => Ok(ContractKind::Check),
+      _ => Err(ParseError {
+        what: "contract kind",
+        value: s.to_string(),
+      }),
+    }
+  }
+}
+
+const KINDS: [ContractKind; 3] = [
+  ContractKind::Requires,
+  ContractKind::Ensures,
+  ContractKind::Check,
+];
+
+static POLICIES: [AtomicU8; 3] = [
+  AtomicU8::new(Policy::Error as u8),
+  AtomicU8::new(Policy::Error as u8),
+  AtomicU8::new(Policy::Error as u8),
+];
+
+static LEVEL: AtomicU8 = AtomicU8::new(AssertionLevel::All as u8);
+
+static FROM_ENV: Once = Once::new();
+
+fn slot(kind: ContractKind) -> &'static AtomicU8 {
+  FROM_ENV.call_once(reload_from_env);
+
+  &POLICIES[kind as usize]
+}
+
+fn level_slot() -> &'static AtomicU8 {
+  FROM_ENV.call_once(reload_from_env);
+
+  &LEVEL
+}
+
+/// The assertion level currently in effect.
+pub fn level() -> AssertionLevel {
+  AssertionLevel::ALL[usize::from(level_slot().load(Ordering::Relaxed))]
+}
+
+/// Sets the assertion level.
+pub fn set_level(level: AssertionLevel) {
+  level_slot().store(level as u8, Ordering::Relaxed);
+}
+
+/// The policy which actually applies to contracts of the given kind, taking the assertion level into account.
+pub(crate) fn effective_policy(kind: ContractKind) -> Policy {
+  if level().enables(kind) {
+    policy(kind)
+  } else {
+    Policy::Disabled
+  }
+}
+
+/// The policy currently in effect for contracts of the given kind.
+pub fn policy(kind: ContractKind) -> Policy {
+  Policy::from_u8(slot(kind).load(Ordering::Relaxed))
+}
+
+/// Sets the policy for contracts of every kind.
+pub fn set_policy(policy: Policy) {
+  for kind in KINDS {
+    set_kind_policy(kind, policy);
+  }
+}
+
+/// Sets the policy for contracts of a single kind.
+pub fn set_kind_policy(kind: ContractKind, policy: Policy) {
+  slot(kind).store(policy.as_u8(), Ordering::Relaxed);
+}
+
+/// Resets the policies and the assertion level to their defaults and then applies the `RUNTIME_CONTRACTS_POLICY` and
+/// `RUNTIME_CONTRACTS_LEVEL` environment variables. This happens automatically the first time either is needed, so it is
+/// only necessary to call this after changing the variables at runtime. Unrecognized values are reported on standard
+/// error and otherwise ignored.
+pub fn reload_from_env() {
+  for kind in KINDS {
+    POLICIES[kind as usize].store(Policy::default().as_u8(), Ordering::Relaxed);
+  }
+
+  LEVEL.store(AssertionLevel::default() as u8, Ordering::Relaxed);
+
+  if let Ok(value) = std::env::var(LEVEL_ENV_VAR) {
+    match value.parse::<AssertionLevel>() {
+      Ok(level) => LEVEL.store(level as u8, Ordering::Relaxed),
+      Err(err) => eprintln!("ignoring {LEVEL_ENV_VAR}: {err}"),
+    }
+  }
+
+  let Ok(value) = std::env::var(POLICY_ENV_VAR) else {
+    return;
+  };
+
+  for entry in value.split(',').filter(|entry| !entry.trim().is_empty()) {
+    let parsed = match entry.split_once('=') {
+      Some((kind, policy)) => kind
+        .parse::<ContractKind>()
+        .and_then(|kind| Ok((Some(kind), policy.parse::<Policy>()?))),
+      None => entry.parse::<Policy>().map(|policy| (None, policy)),
+    };
+
+    match parsed {
+      Ok((Some(kind), policy)) => POLICIES[kind as usize].store(policy.as_u8(), Ordering::Relaxed),
+      Ok((None, policy)) => {
+        for slot in &POLICIES {
+          slot.store(policy.as_u8(), Ordering::Relaxed);
+        }
+      }
+      Err(err) => eprintln!("ignoring {POLICY_ENV_VAR} entry {entry:?}: {err}"),
+    }
+  }
+}
@@ -0,0 +1,222 @@
+//! This module, available with the `problem` feature, converts violations into [RFC 7807](https://www.rfc-editor.org/rfc/rfc7807)
+//! problem details, so that HTTP services report contract failures consistently. By default a violated precondition is the
+//! client's fault and yields `400 Bad Request`, while a violated postcondition or invariant is the server's fault and yields
+//! `500 Internal Server Error`. A contract which could not be evaluated because its predicate failed is no one's fault but
+//! the server's, so it yields `500 Internal Server Error` whatever its kind. Such contracts are a different type of problem
+//! from violations, with a type URI of their own. A [`ProblemMapping`] overrides these statuses and the type URIs for each
+//! kind of contract.
+//!
+//! Problem details serialize to the `application/problem+json` document defined by the RFC, with the kind of contract as an
+//! extension member. Only the contract's message is used as the detail; source locations and context stay on the server.
+//!
+//! ```
+//! use runtime_contracts::problem::{ProblemDetails, ProblemMapping};
+//! use runtime_contracts::{requires, ContractKind};
+//!
+//! let err = requires(|| false, "amount must be positive").unwrap_err();
+//!
+//! let problem = ProblemDetails::from(&err);
+//! assert_eq!(problem.status(), 400);
+//!
+//! let mapping = ProblemMapping::default().with_status(ContractKind::Requires, 422);
+//! let problem = mapping.problem(&err);
+//! assert_eq!(problem.status(), 422);
+//!
+//! assert_eq!(
+//!   serde_json::to_value(&problem).unwrap(),
+//!   serde_json::json!({
+//!     "type": "urn:runtime-contracts:violation:requires",
+//!     "title": "Precondition violated",
+//!     "status": 422,
+//!     "detail": "amount must be positive",
+//!     "kind": "requires",
+//!   })
+//! );
+//! ```
+use serde::{Deserialize, Serialize};
+
+use crate::contract::ContractKind;
+use crate::error::RuntimeContractError;
+
+/// The media type of a serialized [`ProblemDetails`].
+pub const CONTENT_TYPE: &str = "application/problem+json";
+
+/// The status and type URI used for each kind of contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProblemMapping {
+  statuses: [u16; 3],
+  unevaluated_status: u16,
+  type_uris: [String; 3],
+  unevaluated_type_uris: [String; 3],
+}
+
+impl Default for ProblemMapping {
+  fn default() -> Self {
+    Self {
+      statuses: [400, 500, 500],
+      unevaluated_status: 500,
+      type_uris: [
+        ContractKind::Requires,
+        ContractKind::Ensures,
+        ContractKind::Check,
+      ]
+      .map(|kind| format!("urn:runtime-contracts:violation:{kind}")),
+      unevaluated_type_uris: [
+        ContractKind::Requires,
+        ContractKind::Ensures,
+        ContractKind::Check,
+      ]
+      .map(|kind| format!("urn:runtime-contracts:unevaluated:{kind}")),
+    }
+  }
+}
+
+impl ProblemMapping {
+  /// Uses the given HTTP status for violations of contracts of a kind.
+  pub fn with_status(mut self, kind: ContractKind, status: u16) -> Self {
+    self.statuses[kind as usize] = status;
+
+    self
+  }
+
+  /// Uses the given HTTP status for contracts of any kind which could not be evaluated.
+  pub fn with_unevaluated_status(mut self, status: u16) -> Self {
+    self.unevaluated_status = status;
+
+    self
+  }
+
+  /// Uses the given URI to identify the type of problem for violations of contracts of a kind.
+  pub fn with_type_uri<U>(mut self, kind: ContractKind, uri: U) -> Self
+  where
+    U: Into<String>,
+  {
+    self.type_uris[kind as usize] = uri.into();
+
+    self
+  }
+
+  /// Uses the given URI to identify the type of problem for contracts of a kind which could not be evaluated.
+  pub fn with_unevaluated_type_uri<U>(mut self, kind: ContractKind, uri: U) -> Self
+  where
+    U: Into<String>,
+  {
+    self.unevaluated_type_uris[kind as usize] = uri.into();
+
+    self
+  }
+
+  /// The HTTP status used for violations of contracts of a kind.
+  pub fn status(&self, kind: ContractKind) -> u16 {
+    self.statuses[kind as usize]
+  }
+
+  /// The HTTP status used for contracts which could not be evaluated.
+  pub fn unevaluated_status(&self) -> u16 {
+    self.unevaluated_status
+  }
+
+  /// The type URI used for violations of contracts of a kind.
+  pub fn type_uri(&self, kind: ContractKind) -> &str {
+    &self.type_uris[kind as usize]
+  }
+
+  /// The type URI used for contracts of a kind which could not be evaluated.
+  pub fn unevaluated_type_uri(&self, kind: ContractKind) -> &str {
+    &self.unevaluated_type_uris[kind as usize]
+  }
+
+  /// Describes a violation, or a contract which could not be evaluated, as problem details according to this mapping.
+  pub fn problem(&self, error: &RuntimeContractError) -> ProblemDetails {
+    let kind = error.kind();
+    let title = match (kind, error.is_violation()) {
+      (ContractKind::Requires, true) => "Precondition violated",
+      (ContractKind::Ensures, true) => "Postcondition violated",
+      (ContractKind::Check, true) => "Invariant violated",
+      (ContractKind::Requires, false) => "Precondition could not be evaluated",
+      (ContractKind::Ensures, false) => "Postcondition could not be evaluated",
+      (ContractKind::Check, false) => "Invariant could not be evaluated",
+    };
+    let (status, type_uri) = if error.is_violation() {
+      (self.status(kind), self.type_uri(kind))
+    } else {
+      (self.unevaluated_status, self.unevaluated_type_uri(kind))
+    };
+
+    ProblemDetails {
+      type_uri: type_uri.to_string(),
+      title: title.to_string(),
+      status,
+      detail: error.message().to_string(),
+      instance: None,
+      kind,
+    }
+  }
+}
+
+/// A problem details document describing a violated contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProblemDetails {
+  #[serde(rename = "type")]
+  type_uri: String,
+  title: String,
+  status: u16,
+  detail: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  instance: Option<String>,
+  kind: ContractKind,
+}
+
+impl ProblemDetails {
+  /// Records a URI identifying this specific occurrence of the problem, such as the path of the request.
+  pub fn with_instance<I>(mut self, instance: I) -> Self
+  where
+    I: Into<String>,
+  {
+    self.instance = Some(instance.into());
+
+    self
+  }
+
+  /// A URI identifying the type of problem.
+  pub fn type_uri(&self) -> &str {
+    &self.type_uri
+  }
+
+  /// A short summary of the type of problem.
+  pub fn title(&self) -> &str {
+    &self.title
+  }
+
+  /// The HTTP status code.
+  pub fn status(&self) -> u16 {
+    self.status
+  }
+
+  /// An explanation of this occurrence of the problem, which is the violated contract's message.
+  pub fn detail(&self) -> &str {
+    &self.detail
+  }
+
+  /// A URI identifying this occurrence of the problem, if one was recorded.
+  pub fn instance(&self) -> Option<&str> {
+    self.instance.as_deref()
+  }
+
+  /// The kind of contract which was violated.
+  pub fn kind(&self) -> ContractKind {
+    self.kind
+  }
+}
+
+impl From<&RuntimeContractError> for ProblemDetails {
+  fn from(error: &RuntimeContractError) -> Self {
+    ProblemMapping::default().problem(error)
+  }
+}
+
+impl From<RuntimeContractError> for ProblemDetails {
+  fn from(error: RuntimeContractError) -> Self {
+    Self::from(&error)
+  }
+}
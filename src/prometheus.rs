@@ -0,0 +1,112 @@
+//! This module renders the statistics kept by the [`stats`](mod@crate::stats) module in the Prometheus text exposition
+//! format, so that they can be served from an existing `/metrics` endpoint.
+//!
+//! Every contract is labelled with its `kind`, `module`, and `message`. Sites sharing all three labels, such as the same
+//! contract checked from several lines of one module, are added together. The module is empty for contracts expressed with
+//! the utility functions rather than the crate's macros, since only the macros know it. As a location only keeps separate
+//! counters for a bounded number of messages, a message built at runtime adds at most
+//! [`MAX_MESSAGES`](crate::stats::MAX_MESSAGES) series per contract.
+//!
+//! ```
+//! use runtime_contracts::{prometheus, requires};
+//!
+//! let _ = requires(|| 1 > 2, "one must exceed two");
+//!
+//! let metrics = prometheus::render(&runtime_contracts::stats());
+//!
+//! assert!(metrics.contains(
+//!   r#"runtime_contracts_violations_total{kind="requires",module="",message="one must exceed two"} 1"#
+//! ));
+//! ```
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::stats::StatsSnapshot;
+
+/// A metric rendered for each contract.
+struct Metric {
+  name: &'static str,
+  help: &'static str,
+  value: fn(&Counts) -> u64,
+}
+
+const METRICS: [Metric; 4] = [
+  Metric {
+    name: "runtime_contracts_evaluations_total",
+    help: "Contract predicates evaluated.",
+    value: |counts| counts.evaluated,
+  },
+  Metric {
+    name: "runtime_contracts_violations_total",
+    help: "Contracts found to be violated.",
+    value: |counts| counts.failed,
+  },
+  Metric {
+    name: "runtime_contracts_unevaluated_total",
+    help: "Contracts which could not be evaluated because their predicate returned an error.",
+    value: |counts| counts.errored,
+  },
+  Metric {
+    name: "runtime_contracts_skipped_total",
+    help: "Contract evaluations skipped by the policy, the assertion level, or a cost gate.",
+    value: |counts| counts.skipped,
+  },
+];
+
+#[derive(Default)]
+struct Counts {
+  evaluated: u64,
+  failed: u64,
+  errored: u64,
+  skipped: u64,
+}
+
+/// Renders a snapshot of the contract statistics in the Prometheus text exposition format.
+pub fn render(snapshot: &StatsSnapshot) -> String {
+  let mut contracts: BTreeMap<String, Counts> = BTreeMap::new();
+
+  for site in snapshot {
+    let labels = format!(
+      r#"kind="{}",module="{}",message="{}""#,
+      site.kind(),
+      escape(site.location().module_path().unwrap_or_default()),
+      escape(site.message()),
+    );
+    let counts = contracts.entry(labels).or_default();
+
+    counts.evaluated += site.evaluated();
+    counts.failed += site.failed();
+    counts.errored += site.errored();
+    counts.skipped += site.skipped();
+  }
+
+  let mut output = String::new();
+
+  for Metric { name, help, value } in METRICS {
+    // Writing to a `String` cannot fail.
+    let _ = writeln!(output, "# HELP {name} {help}");
+    let _ = writeln!(output, "# TYPE {name} counter");
+
+    for (labels, counts) in &contracts {
+      let _ = writeln!(output, "{name}{{{labels}}} {}", value(counts));
+    }
+  }
+
+  output
+}
+
+/// Escapes a label value as required by the exposition format.
+fn escape(value: &str) -> String {
+  let mut escaped = String::with_capacity(value.len());
+
+  for c in value.chars() {
+    match c {
+      '\\' => escaped.push_str(r"\\"),
+      '"' => escaped.push_str(r#"\""#),
+      '\n' => escaped.push_str(r"\n"),
+      c => escaped.push(c),
+    }
+  }
+
+  escaped
+}
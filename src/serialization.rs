@@ -0,0 +1,279 @@
+//! This module, available with the `serde` feature, implements `Serialize` and `Deserialize` for the crate's errors so that
+//! they can be stored or sent across process boundaries. The schema below is stable: fields may be added in future
+//! versions, but existing fields will keep their names and meaning, and unknown fields are ignored when deserializing.
+//!
+//! A [`RuntimeContractError`] is an object tagged by the kind of contract violated:
+//!
+//! | Field       | Type   | Presence                       | Meaning                                                              |
+//! |-------------|--------|--------------------------------|----------------------------------------------------------------------|
+//! | `kind`      | string | always                         | `"requires"`, `"ensures"`, or `"check"`                              |
+//! | `message`   | string | always                         | the message given when the contract was declared                     |
+//! | `predicate` | string | when known                     | the source text of the predicate                                     |
+//! | `location`  | object | when known                     | where the contract was evaluated, as described below                 |
+//! | `context`   | object | when any was in scope          | the [`crate::context`] entries in scope, from outermost to innermost |
+//! | `blame`     | object | always                         | the party at fault, as described below                               |
+//! | `cause`     | string | when it could not be evaluated | the message of the error returned by the predicate                   |
+//!
+//! A location is an object with a `file` string, `line` and `column` numbers starting from 1, and, when known, a
+//! `module_path` string. Blame is an object with a `party` string, which is `"caller"`, `"callee"`, or `"module"` as
+//! determined by the kind, and, when one is known, a `label` string identifying that party. A [`ContractKind`] on its own
+//! is serialized as its name, and [`ContractViolations`] as an array of errors.
+//!
+//! An error with a `cause` is a [`RuntimeContractError::EvaluationFailure`]. Only the message of the predicate's error is
+//! kept, so a deserialized error's [`source`](std::error::Error::source) can be displayed but not downcast.
+//!
+//! ```
+//! use runtime_contracts::check;
+//! use runtime_contracts::error::RuntimeContractError;
+//!
+//! let err = check(|| false, "balance must not be negative").unwrap_err();
+//! let json = serde_json::to_value(&err).unwrap();
+//!
+//! assert_eq!(json["kind"], "check");
+//! assert_eq!(json["message"], "balance must not be negative");
+//! assert_eq!(json["location"]["file"], file!());
+//! assert_eq!(serde_json::from_value::<RuntimeContractError>(json).unwrap(), err);
+//! ```
+use std::error::Error;
+use std::fmt;
+
+use serde::de::{self, MapAccess, Visitor};
+use serde::ser::{SerializeMap, SerializeStruct};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::blame::Blame;
+use crate::contract::ContractKind;
+use crate::error::{ContractFailure, ContractViolations, RuntimeContractError, SourceLocation};
+
+impl Serialize for ContractKind {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.collect_str(self)
+  }
+}
+
+impl<'de> Deserialize<'de> for ContractKind {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    String::deserialize(deserializer)?
+      .parse()
+      .map_err(de::Error::custom)
+  }
+}
+
+impl Serialize for SourceLocation {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let mut state = serializer.serialize_struct("SourceLocation", 4)?;
+
+    state.serialize_field("file", self.file())?;
+    state.serialize_field("line", &self.line())?;
+    state.serialize_field("column", &self.column())?;
+
+    match self.module_path() {
+      Some(module_path) => state.serialize_field("module_path", module_path)?,
+      None => state.skip_field("module_path")?,
+    }
+
+    state.end()
+  }
+}
+
+#[derive(Deserialize)]
+struct LocationFields {
+  file: String,
+  line: u32,
+  column: u32,
+  #[serde(default)]
+  module_path: Option<String>,
+}
+
+impl<'de> Deserialize<'de> for SourceLocation {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let fields = LocationFields::deserialize(deserializer)?;
+    let location = SourceLocation::new(fields.file, fields.line, fields.column);
+
+    Ok(match fields.module_path {
+      Some(module_path) => location.with_module_path(module_path),
+      None => location,
+    })
+  }
+}
+
+/// Serializes context entries as an object, keeping their order.
+struct ContextMap<'a>(&'a [(String, String)]);
+
+impl Serialize for ContextMap<'_> {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let mut map = serializer.serialize_map(Some(self.0.len()))?;
+
+    for (key, value) in self.0 {
+      map.serialize_entry(key, value)?;
+    }
+
+    map.end()
+  }
+}
+
+/// Deserializes context entries from an object, keeping their order.
+fn deserialize_context<'de, D>(deserializer: D) -> Result<Vec<(String, String)>, D::Error>
+where
+  D: Deserializer<'de>,
+{
+  struct ContextVisitor;
+
+  impl<'de> Visitor<'de> for ContextVisitor {
+    type Value = Vec<(String, String)>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      f.write_str("an object of context entries")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+      A: MapAccess<'de>,
+    {
+      let mut entries = Vec::with_capacity(map.size_hint().unwrap_or_default());
+
+      while let Some(entry) = map.next_entry()? {
+        entries.push(entry);
+      }
+
+      Ok(entries)
+    }
+  }
+
+  deserializer.deserialize_map(ContextVisitor)
+}
+
+impl Serialize for RuntimeContractError {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    let failure = self.failure();
+    let mut state = serializer.serialize_struct("RuntimeContractError", 7)?;
+
+    state.serialize_field("kind", &self.kind())?;
+    state.serialize_field("message", failure.message())?;
+
+    match failure.predicate() {
+      Some(predicate) => state.serialize_field("predicate", predicate)?,
+      None => state.skip_field("predicate")?,
+    }
+
+    match failure.location() {
+      Some(location) => state.serialize_field("location", location)?,
+      None => state.skip_field("location")?,
+    }
+
+    if failure.context().is_empty() {
+      state.skip_field("context")?;
+    } else {
+      state.serialize_field("context", &ContextMap(failure.context()))?;
+    }
+
+    state.serialize_field("blame", &BlameFields::from(self.blame()))?;
+
+    match self.source() {
+      Some(cause) => state.serialize_field("cause", &cause.to_string())?,
+      None => state.skip_field("cause")?,
+    }
+
+    state.end()
+  }
+}
+
+#[derive(Serialize, Deserialize)]
+struct BlameFields {
+  party: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  label: Option<String>,
+}
+
+impl From<Blame> for BlameFields {
+  fn from(blame: Blame) -> Self {
+    Self {
+      party: blame.party().to_string(),
+      label: blame.label().map(str::to_string),
+    }
+  }
+}
+
+#[derive(Deserialize)]
+struct ErrorFields {
+  kind: ContractKind,
+  message: String,
+  #[serde(default)]
+  predicate: Option<String>,
+  #[serde(default)]
+  location: Option<SourceLocation>,
+  #[serde(default, deserialize_with = "deserialize_context")]
+  context: Vec<(String, String)>,
+  // The party is implied by the kind, so only the label needs restoring.
+  #[serde(default)]
+  blame: Option<BlameFields>,
+  #[serde(default)]
+  cause: Option<String>,
+}
+
+impl<'de> Deserialize<'de> for RuntimeContractError {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    let fields = ErrorFields::deserialize(deserializer)?;
+    let mut failure = ContractFailure::new(fields.message);
+
+    if let Some(location) = fields.location {
+      failure = failure.with_location(location);
+    }
+
+    if let Some(predicate) = fields.predicate {
+      failure = failure.with_predicate(predicate);
+    }
+
+    for (key, value) in fields.context {
+      failure = failure.with_context(key, value);
+    }
+
+    if let Some(label) = fields.blame.and_then(|blame| blame.label) {
+      failure = failure.with_blame_label(label);
+    }
+
+    Ok(match fields.cause {
+      Some(cause) => RuntimeContractError::unevaluated(fields.kind, failure, cause),
+      None => RuntimeContractError::from_failure(fields.kind, failure),
+    })
+  }
+}
+
+impl Serialize for ContractViolations {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: Serializer,
+  {
+    serializer.collect_seq(self)
+  }
+}
+
+impl<'de> Deserialize<'de> for ContractViolations {
+  fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+  where
+    D: Deserializer<'de>,
+  {
+    Vec::<RuntimeContractError>::deserialize(deserializer).map(ContractViolations::from_iter)
+  }
+}
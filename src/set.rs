@@ -0,0 +1,124 @@
+//! This module contains [`ContractSet`], which evaluates many contracts without stopping at the first violation. This is
+//! most useful when validating forms or configuration, where reporting every problem at once saves a round trip per error.
+use std::fmt;
+
+use crate::contract::{self, ContractKind, RuntimeContract, Site};
+use crate::error::{ContractViolations, RuntimeContractError};
+use crate::Result;
+
+/// Evaluates contracts eagerly and accumulates every violation rather than short-circuiting like `?` does.
+///
+/// # Examples
+///
+/// ```
+/// use runtime_contracts::set::ContractSet;
+///
+/// let name = "";
+/// let age = 12;
+///
+/// let violations = ContractSet::new()
+///   .requires(|| !name.is_empty(), "name must not be empty")
+///   .requires(|| age >= 18, "must be an adult")
+///   .requires(|| age < 150, "must be a plausible age")
+///   .finish()
+///   .unwrap_err();
+///
+/// assert_eq!(violations.len(), 2);
+/// assert_eq!(violations.iter().next().unwrap().message(), "name must not be empty");
+/// ```
+#[derive(Debug, Default)]
+pub struct ContractSet {
+  errors: Vec<RuntimeContractError>,
+}
+
+impl ContractSet {
+  /// Creates an empty set, which has no violations.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Evaluates a precondition, recording its violation if it does not hold.
+  #[track_caller]
+  pub fn requires<F, M>(self, pred: F, message: M) -> Self
+  where
+    F: FnOnce() -> bool,
+    M: fmt::Display,
+  {
+    let result = contract::evaluate(ContractKind::Requires, Site::caller(), pred, message);
+
+    self.record(result)
+  }
+
+  /// Evaluates a postcondition against a value, recording its violation if it does not hold.
+  #[track_caller]
+  pub fn ensures<T, F, M>(self, value: &T, predicate: F, message: M) -> Self
+  where
+    T: ?Sized,
+    F: FnOnce(&T) -> bool,
+    M: fmt::Display,
+  {
+    let result = contract::evaluate(
+      ContractKind::Ensures,
+      Site::caller(),
+      || predicate(value),
+      message,
+    );
+
+    self.record(result)
+  }
+
+  /// Evaluates an invariant, recording its violation if it does not hold.
+  #[track_caller]
+  pub fn check<F, M>(self, pred: F, message: M) -> Self
+  where
+    F: FnOnce() -> bool,
+    M: fmt::Display,
+  {
+    let result = contract::evaluate(ContractKind::Check, Site::caller(), pred, message);
+
+    self.record(result)
+  }
+
+  /// Verifies a [`RuntimeContract`] against a value, recording its violation if it does not hold.
+  #[track_caller]
+  pub fn verify<T>(self, contract: &RuntimeContract<T>, value: &T) -> Self
+  where
+    T: ?Sized,
+  {
+    let result = contract.verify(value);
+
+    self.record(result)
+  }
+
+  /// Records the outcome of a contract evaluated elsewhere, such as by [`crate::requires()`].
+  pub fn record<T>(mut self, result: Result<T>) -> Self {
+    if let Err(error) = result {
+      self.errors.push(error);
+    }
+
+    self
+  }
+
+  /// Whether any contract in the set has been violated so far.
+  pub fn is_violated(&self) -> bool {
+    !self.errors.is_empty()
+  }
+
+  /// Finishes validation, yielding every violation if there were any.
+  pub fn finish(self) -> Result<(), ContractViolations> {
+    if self.errors.is_empty() {
+      Ok(())
+    } else {
+      Err(self.errors.into_iter().collect())
+    }
+  }
+}
+
+impl Extend<RuntimeContractError> for ContractSet {
+  fn extend<I>(&mut self, iter: I)
+  where
+    I: IntoIterator<Item = RuntimeContractError>,
+  {
+    self.errors.extend(iter);
+  }
+}
@@ -0,0 +1,373 @@
+//! This module keeps in-process statistics about every contract call site, keyed by where the contract was evaluated and
+//! its message. For each site it counts how many evaluations passed, failed, errored (when a fallible predicate could not
+//! decide whether the contract holds), or were skipped (by the policy, the assertion level, or a cost gate). This reveals
+//! which guards actually fire, which never run, and which fail constantly.
+//!
+//! The counters are atomics and each thread caches the counters of the sites it has seen, so recording an evaluation
+//! takes no locks once a site is known. A lock is only taken the first time a thread sees a site, and when taking a
+//! snapshot.
+//!
+//! A message built at runtime, such as one formatted with the value that was checked, would otherwise give every distinct
+//! value its own site. To bound the memory used, and the number of series [`crate::prometheus`] renders, each location keeps
+//! separate counters for at most [`MAX_MESSAGES`] messages per kind of contract. Any further message is counted at that
+//! location under [`OTHER_MESSAGES`] instead.
+//!
+//! Contracts declared with a caller-defined error, such as [`crate::requires_or`], only build their error once violated,
+//! so their sites are keyed by location alone and have an empty message.
+//!
+//! ```
+//! use runtime_contracts::{requires, stats};
+//!
+//! for i in 0..3 {
+//!   let _ = requires(|| i > 0, "i must be positive");
+//! }
+//!
+//! let snapshot = stats::snapshot();
+//! let site = snapshot.iter().find(|site| site.message() == "i must be positive").unwrap();
+//!
+//! assert_eq!(site.evaluated(), 3);
+//! assert_eq!(site.passed(), 2);
+//! assert_eq!(site.failed(), 1);
+//! ```
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+use crate::contract::{ContractKind, Outcome, Site};
+use crate::error::SourceLocation;
+
+/// The number of distinct messages for which a location keeps separate counters, per kind of contract.
+pub const MAX_MESSAGES: usize = 16;
+
+/// The message under which a location counts the evaluations of every message past the first [`MAX_MESSAGES`].
+pub const OTHER_MESSAGES: &str = "(other messages)";
+
+/// Identifies where a contract was evaluated.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct SiteKey {
+  kind: ContractKind,
+  file: &'static str,
+  line: u32,
+  column: u32,
+  module_path: Option<&'static str>,
+}
+
+/// The counters of each message seen at a location.
+#[derive(Debug, Default)]
+struct SiteMessages {
+  counters: HashMap<String, Arc<SiteCounters>>,
+  other: Option<Arc<SiteCounters>>,
+}
+
+#[derive(Debug, Default)]
+struct SiteCounters {
+  passed: AtomicU64,
+  failed: AtomicU64,
+  errored: AtomicU64,
+  skipped: AtomicU64,
+}
+
+impl SiteCounters {
+  fn counter(&self, outcome: Outcome) -> &AtomicU64 {
+    match outcome {
+      Outcome::Passed => &self.passed,
+      Outcome::Failed => &self.failed,
+      Outcome::Errored => &self.errored,
+      Outcome::Skipped => &self.skipped,
+    }
+  }
+
+  fn read(&self, reset: bool) -> [u64; 4] {
+    let read = |counter: &AtomicU64| {
+      if reset {
+        counter.swap(0, Ordering::Relaxed)
+      } else {
+        counter.load(Ordering::Relaxed)
+      }
+    };
+
+    [
+      read(&self.passed),
+      read(&self.failed),
+      read(&self.errored),
+      read(&self.skipped),
+    ]
+  }
+}
+
+static SITES: RwLock<Option<HashMap<SiteKey, SiteMessages>>> = RwLock::new(None);
+
+/// The counters of a site a thread has already seen.
+struct CachedSite {
+  kind: ContractKind,
+  // `None` for the counters of other messages, which is only cached once a location has no room for further messages.
+  message: Option<String>,
+  counters: Arc<SiteCounters>,
+}
+
+thread_local! {
+  // Keyed by the address of the site's `Location`, which is stable for a given call site.
+  static CACHE: RefCell<HashMap<usize, Vec<CachedSite>>> = RefCell::new(HashMap::new());
+}
+
+/// Whether a message displays as exactly the given text, without allocating.
+fn displays_as(message: &dyn fmt::Display, expected: &str) -> bool {
+  struct Matcher<'a>(&'a str);
+
+  impl Write for Matcher<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+      self.0 = self.0.strip_prefix(s).ok_or(fmt::Error)?;
+
+      Ok(())
+    }
+  }
+
+  let mut matcher = Matcher(expected);
+
+  write!(matcher, "{message}").is_ok() && matcher.0.is_empty()
+}
+
+fn counters(kind: ContractKind, site: &Site, message: &dyn fmt::Display) -> Arc<SiteCounters> {
+  let address = site.location as *const _ as usize;
+
+  CACHE.with(|cache| {
+    let mut cache = cache.borrow_mut();
+    let cached = cache.entry(address).or_default();
+
+    // The counters of other messages are cached last, so every message with its own counters is matched before them.
+    if let Some(hit) = cached.iter().find(|cached| {
+      cached.kind == kind
+        && cached
+          .message
+          .as_deref()
+          .is_none_or(|expected| displays_as(message, expected))
+    }) {
+      return Arc::clone(&hit.counters);
+    }
+
+    let key = SiteKey {
+      kind,
+      file: site.location.file(),
+      line: site.location.line(),
+      column: site.location.column(),
+      module_path: site.module_path,
+    };
+    let message = message.to_string();
+    let mut sites = SITES
+      .write()
+      .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let messages = sites
+      .get_or_insert_with(HashMap::new)
+      .entry(key)
+      .or_default();
+
+    if messages.counters.len() < MAX_MESSAGES || messages.counters.contains_key(&message) {
+      let counters = Arc::clone(messages.counters.entry(message.clone()).or_default());
+
+      cached.push(CachedSite {
+        kind,
+        message: Some(message),
+        counters: Arc::clone(&counters),
+      });
+
+      return counters;
+    }
+
+    // The location is full, so its messages can no longer change: cache all of them, so that only messages without their
+    // own counters fall through to the other messages.
+    for (message, counters) in &messages.counters {
+      if !cached
+        .iter()
+        .any(|cached| cached.kind == kind && cached.message.as_ref() == Some(message))
+      {
+        cached.push(CachedSite {
+          kind,
+          message: Some(message.clone()),
+          counters: Arc::clone(counters),
+        });
+      }
+    }
+
+    let other = Arc::clone(messages.other.get_or_insert_with(Default::default));
+
+    cached.push(CachedSite {
+      kind,
+      message: None,
+      counters: Arc::clone(&other),
+    });
+
+    other
+  })
+}
+
+pub(crate) fn record(
+  kind: ContractKind,
+  site: &Site,
+  message: &dyn fmt::Display,
+  outcome: Outcome,
+) {
+  let message = if site.lazy_message { &"" } else { message };
+
+  counters(kind, site, message)
+    .counter(outcome)
+    .fetch_add(1, Ordering::Relaxed);
+}
+
+/// The statistics of a single contract call site at the time a snapshot was taken.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiteStats {
+  kind: ContractKind,
+  location: SourceLocation,
+  message: String,
+  passed: u64,
+  failed: u64,
+  errored: u64,
+  skipped: u64,
+}
+
+impl SiteStats {
+  /// The kind of contract evaluated at this site.
+  pub fn kind(&self) -> ContractKind {
+    self.kind
+  }
+
+  /// Where the contract was evaluated.
+  pub fn location(&self) -> &SourceLocation {
+    &self.location
+  }
+
+  /// The contract's message.
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+
+  /// How many times the contract's predicate was actually run, whether or not it could decide the contract.
+  pub fn evaluated(&self) -> u64 {
+    self.passed + self.failed + self.errored
+  }
+
+  /// How many evaluations found the contract to hold.
+  pub fn passed(&self) -> u64 {
+    self.passed
+  }
+
+  /// How many evaluations found the contract to be violated.
+  pub fn failed(&self) -> u64 {
+    self.failed
+  }
+
+  /// How many evaluations could not decide the contract because its predicate returned an error. These are not counted as
+  /// failures.
+  pub fn errored(&self) -> u64 {
+    self.errored
+  }
+
+  /// How many evaluations were skipped without running the predicate.
+  pub fn skipped(&self) -> u64 {
+    self.skipped
+  }
+
+  fn sort_key(&self) -> (&str, u32, u32, &str) {
+    (
+      self.location.file(),
+      self.location.line(),
+      self.location.column(),
+      &self.message,
+    )
+  }
+}
+
+/// The statistics of every contract call site seen so far, ordered by location and then message.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatsSnapshot {
+  sites: Vec<SiteStats>,
+}
+
+impl StatsSnapshot {
+  /// Iterates over the statistics of each site.
+  pub fn iter(&self) -> std::slice::Iter<'_, SiteStats> {
+    self.sites.iter()
+  }
+
+  /// The number of sites.
+  pub fn len(&self) -> usize {
+    self.sites.len()
+  }
+
+  /// Whether no sites have been seen.
+  pub fn is_empty(&self) -> bool {
+    self.sites.is_empty()
+  }
+}
+
+impl<'a> IntoIterator for &'a StatsSnapshot {
+  type Item = &'a SiteStats;
+  type IntoIter = std::slice::Iter<'a, SiteStats>;
+
+  fn into_iter(self) -> Self::IntoIter {
+    self.sites.iter()
+  }
+}
+
+fn collect(reset: bool) -> StatsSnapshot {
+  let sites = SITES
+    .read()
+    .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+  let mut sites: Vec<_> = sites
+    .iter()
+    .flatten()
+    .flat_map(|(key, messages)| {
+      let other = messages
+        .other
+        .iter()
+        .map(|counters| (OTHER_MESSAGES, counters));
+
+      messages
+        .counters
+        .iter()
+        .map(|(message, counters)| (message.as_str(), counters))
+        .chain(other)
+        .map(move |(message, counters)| {
+          let [passed, failed, errored, skipped] = counters.read(reset);
+          let mut location = SourceLocation::new(key.file, key.line, key.column);
+
+          if let Some(module_path) = key.module_path {
+            location = location.with_module_path(module_path);
+          }
+
+          SiteStats {
+            kind: key.kind,
+            location,
+            message: message.to_string(),
+            passed,
+            failed,
+            errored,
+            skipped,
+          }
+        })
+    })
+    .collect();
+
+  sites.sort_by(|a, b| a.sort_key().cmp(&b.sort_key()));
+
+  StatsSnapshot { sites }
+}
+
+/// Takes a snapshot of the statistics of every site seen so far.
+pub fn snapshot() -> StatsSnapshot {
+  collect(false)
+}
+
+/// Takes a snapshot of the statistics of every site seen so far and resets their counters to zero, so that the next
+/// snapshot only covers evaluations made in between.
+pub fn take() -> StatsSnapshot {
+  collect(true)
+}
+
+/// Resets the counters of every site to zero.
+pub fn reset() {
+  collect(true);
+}
@@ -0,0 +1,95 @@
+//! This module integrates with [`tracing`](https://docs.rs/tracing). It is only available with the `tracing` feature
+//! enabled.
+//!
+//! Every evaluation emits a `TRACE` event with the `runtime_contracts` target recording the contract's kind, message,
+//! location, and outcome (`passed`, `failed`, `errored`, or `skipped`). Every violation additionally emits an event at a configurable
+//! level, `ERROR` by default. Events are emitted within the current span, so violations can be correlated with whatever
+//! request or task was being handled at the time.
+//!
+//! The contract's message is recorded as `contract_message`, since `message` is the event's own. Fields which are unknown,
+//! such as the module of a contract expressed without the crate's macros, are left out rather than recorded as empty.
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use tracing::{field, Level};
+
+use crate::contract::{ContractKind, Outcome, Site};
+use crate::error::RuntimeContractError;
+
+const LEVELS: [Level; 5] = [
+  Level::ERROR,
+  Level::WARN,
+  Level::INFO,
+  Level::DEBUG,
+  Level::TRACE,
+];
+
+static VIOLATION_LEVEL: AtomicU8 = AtomicU8::new(0);
+
+/// The level at which violations are reported.
+pub fn violation_level() -> Level {
+  LEVELS[usize::from(VIOLATION_LEVEL.load(Ordering::Relaxed))]
+}
+
+/// Sets the level at which violations are reported.
+pub fn set_violation_level(level: Level) {
+  let index = LEVELS
+    .iter()
+    .position(|candidate| *candidate == level)
+    .unwrap_or_default();
+
+  VIOLATION_LEVEL.store(index as u8, Ordering::Relaxed);
+}
+
+/// Emits an event at a level only known at runtime, since `tracing::event!` requires a constant one.
+macro_rules! event_at {
+  ($level:expr, $($fields:tt)+) => {
+    match $level {
+      Level::ERROR => tracing::event!(target: "runtime_contracts", Level::ERROR, $($fields)+),
+      Level::WARN => tracing::event!(target: "runtime_contracts", Level::WARN, $($fields)+),
+      Level::INFO => tracing::event!(target: "runtime_contracts", Level::INFO, $($fields)+),
+      Level::DEBUG => tracing::event!(target: "runtime_contracts", Level::DEBUG, $($fields)+),
+      Level::TRACE => tracing::event!(target: "runtime_contracts", Level::TRACE, $($fields)+),
+    }
+  };
+}
+
+pub(crate) fn evaluated(
+  kind: ContractKind,
+  site: &Site,
+  message: &dyn fmt::Display,
+  outcome: Outcome,
+) {
+  // A caller-defined error is only built once its contract has failed, so it is left out of other outcomes.
+  let message = (!site.lazy_message || outcome == Outcome::Failed).then(|| field::display(message));
+
+  tracing::event!(
+    target: "runtime_contracts",
+    Level::TRACE,
+    kind = %kind,
+    contract_message = message,
+    file = site.location.file(),
+    line = site.location.line(),
+    column = site.location.column(),
+    module_path = site.module_path,
+    outcome = %outcome,
+    "contract evaluated"
+  );
+}
+
+pub(crate) fn violated(error: &RuntimeContractError) {
+  let location = error.location();
+
+  event_at!(
+    violation_level(),
+    kind = %error.kind(),
+    contract_message = error.message(),
+    predicate = error.predicate(),
+    file = location.map(|location| location.file()),
+    line = location.map(|location| location.line()),
+    column = location.map(|location| location.column()),
+    module_path = location.and_then(|location| location.module_path()),
+    error = %error,
+    "contract violated"
+  );
+}
@@ -0,0 +1,135 @@
+#![cfg(feature = "async")]
+
+use std::cell::Cell;
+
+use futures::executor::block_on;
+use futures::{stream, StreamExt};
+use pretty_assertions::assert_eq;
+
+use runtime_contracts::asynchronous::{
+  check_async, ensures_async, requires_async, FutureContracts, StreamContracts,
+};
+use runtime_contracts::error::RuntimeContractError;
+use runtime_contracts::iter::INDEX;
+use runtime_contracts::ContractKind;
+
+async fn exists(id: u32) -> bool {
+  id < 10
+}
+
+#[test]
+fn async_predicates_are_awaited() {
+  block_on(async {
+    assert_eq!(requires_async(exists(1), "must exist").await, Ok(()));
+    assert_eq!(check_async(exists(2), "must exist").await, Ok(()));
+    assert_eq!(
+      ensures_async(3, |id| exists(*id), "must exist").await,
+      Ok(3)
+    );
+  });
+}
+
+#[test]
+fn failing_async_predicates_produce_errors_of_their_kind() {
+  block_on(async {
+    let requires = requires_async(exists(10), "must exist").await.unwrap_err();
+    let ensures = ensures_async(11, |id| exists(*id), "must exist")
+      .await
+      .unwrap_err();
+    let check = check_async(exists(12), "must exist").await.unwrap_err();
+
+    assert_eq!(requires.kind(), ContractKind::Requires);
+    assert_eq!(ensures.kind(), ContractKind::Ensures);
+    assert_eq!(check.kind(), ContractKind::Check);
+    assert_eq!(requires.message(), "must exist");
+  });
+}
+
+#[test]
+fn violations_are_located_where_the_contract_was_created() {
+  let line = line!() + 1;
+  let contract = requires_async(exists(10), "must exist");
+  let err = block_on(contract).unwrap_err();
+
+  assert_eq!(err.location().unwrap().file(), file!());
+  assert_eq!(err.location().unwrap().line(), line);
+}
+
+#[test]
+fn predicates_are_not_evaluated_until_awaited() {
+  let created = Cell::new(false);
+  let contract = ensures_async(
+    1,
+    |id| {
+      created.set(true);
+      exists(*id)
+    },
+    "must exist",
+  );
+
+  assert!(!created.get());
+  assert_eq!(block_on(contract), Ok(1));
+  assert!(created.get());
+}
+
+#[test]
+fn futures_check_their_output() {
+  block_on(async {
+    assert_eq!(
+      async { 4 }.ensures(|i| i % 2 == 0, "must be even").await,
+      Ok(4)
+    );
+
+    let err = async { 5 }
+      .ensures(|i| i % 2 == 0, "must be even")
+      .await
+      .unwrap_err();
+
+    assert_eq!(err.kind(), ContractKind::Ensures);
+    assert_eq!(err.message(), "must be even");
+  });
+}
+
+#[test]
+fn streams_check_every_item() {
+  let items: Vec<_> = block_on(
+    stream::iter([1, 2, 3])
+      .requires_each(|i| *i > 0, "must be positive")
+      .collect(),
+  );
+
+  assert_eq!(items, vec![Ok(1), Ok(2), Ok(3)]);
+}
+
+#[test]
+fn streams_stop_at_the_first_violation() {
+  let items: Vec<_> = block_on(
+    stream::iter([1, -2, 3, -4])
+      .check_each(|i| *i > 0, "must be positive")
+      .collect(),
+  );
+
+  assert_eq!(items.len(), 2);
+  assert_eq!(items[0], Ok(1));
+
+  let err = items[1].as_ref().unwrap_err();
+
+  assert_eq!(err.kind(), ContractKind::Check);
+  assert_eq!(
+    err.failure().context(),
+    &[(INDEX.to_string(), "1".to_string())]
+  );
+}
+
+#[test]
+fn stream_adapters_produce_errors_of_their_kind() {
+  let kind = |items: Vec<Result<i32, RuntimeContractError>>| {
+    items.into_iter().find_map(Result::err).unwrap().kind()
+  };
+
+  let requires = block_on(stream::iter([-1]).requires_each(|i| *i > 0, "").collect());
+  let ensures = block_on(stream::iter([-1]).ensures_each(|i| *i > 0, "").collect());
+
+  assert_eq!(kind(requires), ContractKind::Requires);
+  assert_eq!(kind(ensures), ContractKind::Ensures);
+}
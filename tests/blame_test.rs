@@ -0,0 +1,95 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use runtime_contracts::blame::{self, Blame, Party};
+use runtime_contracts::{check, ensures, requires, RuntimeContract};
+
+mod ledger {
+  pub mod accounts {
+    pub fn check_balance(balance: i64) -> runtime_contracts::Result<()> {
+      runtime_contracts::check!(balance >= 0, "balance must not be negative")
+    }
+  }
+}
+
+#[test]
+fn blame_follows_the_kind_of_contract() {
+  let parties: Vec<_> = [
+    requires(|| false, "bad input").unwrap_err(),
+    ensures(1, |i| *i > 1, "bad output").unwrap_err(),
+    check(|| false, "bad state").unwrap_err(),
+  ]
+  .iter()
+  .map(|err| err.blame().party())
+  .collect();
+
+  assert_eq!(parties, vec![Party::Caller, Party::Callee, Party::Module]);
+}
+
+#[test]
+fn parties_are_identified_by_module_path() {
+  let _guard = common::serial();
+  let err = requires!(1 > 2, "one must exceed two").unwrap_err();
+
+  assert_eq!(
+    err.blame(),
+    Blame::new(Party::Caller, None, Some("blame_test".to_string()))
+  );
+  assert_eq!(err.blame().to_string(), "caller of blame_test");
+
+  let err = ensures!(1, |i| *i > 1, "must exceed one").unwrap_err();
+
+  assert_eq!(err.blame().to_string(), "callee blame_test");
+  assert_eq!(
+    check(|| false, "no module")
+      .unwrap_err()
+      .blame()
+      .to_string(),
+    "unknown module"
+  );
+}
+
+#[test]
+fn contracts_can_name_the_party_to_blame() {
+  let contract =
+    RuntimeContract::requires(|i: &i32| *i > 0, "must be positive").with_blame("checkout-service");
+  let err = contract.verify(&-1).unwrap_err();
+
+  assert_eq!(contract.blame(), Some("checkout-service"));
+  assert_eq!(err.blame().party(), Party::Caller);
+  assert_eq!(err.blame().label(), Some("checkout-service"));
+  assert_eq!(err.blame().to_string(), "checkout-service");
+
+  let combined = RuntimeContract::requires(|i: &i32| *i < 10, "must be small").and(contract);
+
+  assert_eq!(combined.blame(), Some("checkout-service"));
+}
+
+#[test]
+fn module_labels_identify_callees_and_modules() {
+  let _guard = common::serial();
+
+  blame::label_module("blame_test", "platform-team");
+  blame::label_module("blame_test::ledger", "ledger-team");
+
+  let module = ledger::accounts::check_balance(-1).unwrap_err();
+  let callee = ensures!(1, |i| *i > 1, "must exceed one").unwrap_err();
+  let caller = requires!(1 > 2, "one must exceed two").unwrap_err();
+  let explicit = RuntimeContract::check(|_: &()| false, "must hold")
+    .with_blame("explicit")
+    .verify(&())
+    .unwrap_err();
+
+  blame::clear_module_labels();
+
+  assert_eq!(module.blame().label(), Some("ledger-team"));
+  assert_eq!(
+    module.blame().module_path(),
+    Some("blame_test::ledger::accounts")
+  );
+  assert_eq!(callee.blame().label(), Some("platform-team"));
+  assert_eq!(caller.blame().label(), None);
+  assert_eq!(explicit.blame().label(), Some("explicit"));
+  assert_eq!(check!(1 > 2).unwrap_err().blame().label(), None);
+}
@@ -0,0 +1,13 @@
+//! Helpers shared by the integration tests.
+use std::sync::{Mutex, MutexGuard};
+
+// Policies, gates, hooks, statistics, and the like are global, so tests which depend on them must not run concurrently.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+/// Keeps other tests which depend on global state from running until the guard is dropped. A test which panics while
+/// holding it does not fail the others.
+pub fn serial() -> MutexGuard<'static, ()> {
+  SERIAL
+    .lock()
+    .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
@@ -0,0 +1,61 @@
+use std::thread;
+
+use pretty_assertions::assert_eq;
+
+use runtime_contracts::{check, context};
+
+fn entries(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+  pairs
+    .iter()
+    .map(|(key, value)| (key.to_string(), value.to_string()))
+    .collect()
+}
+
+#[test]
+fn failures_capture_the_context_in_scope() {
+  let _request = context::scope("request_id", 42);
+  let err = {
+    let _operation = context::scope("operation", "withdraw");
+
+    check(|| false, "must hold").unwrap_err()
+  };
+
+  assert_eq!(
+    err.failure().context(),
+    entries(&[("request_id", "42"), ("operation", "withdraw")])
+  );
+  assert_eq!(
+    check(|| false, "must hold")
+      .unwrap_err()
+      .failure()
+      .context(),
+    entries(&[("request_id", "42")])
+  );
+}
+
+#[test]
+fn context_ends_with_its_guard() {
+  let outer = context::scope("outer", 1);
+  let _inner = context::scope("inner", 2);
+
+  drop(outer);
+
+  assert_eq!(context::current(), Vec::new());
+  assert_eq!(
+    check(|| false, "must hold")
+      .unwrap_err()
+      .failure()
+      .context(),
+    &[]
+  );
+}
+
+#[test]
+fn context_is_per_thread() {
+  let _request = context::scope("request_id", "main");
+
+  let seen = thread::spawn(context::current).join().unwrap();
+
+  assert_eq!(seen, Vec::new());
+  assert_eq!(context::current(), entries(&[("request_id", "main")]));
+}
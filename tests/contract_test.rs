@@ -0,0 +1,177 @@
+use pretty_assertions::assert_eq;
+
+use runtime_contracts::error::RuntimeContractError;
+use runtime_contracts::{ContractKind, RuntimeContract};
+
+#[test]
+fn contract_verifies_with_truthy_predicate() {
+  let contract = RuntimeContract::requires(|i: &i32| *i > 0, "should always pass");
+
+  assert_eq!(contract.verify(&1), Ok(()));
+}
+
+#[test]
+fn contract_fails_with_kind_specific_error() {
+  let requires = RuntimeContract::requires(|i: &i32| *i > 0, "must be positive");
+  let ensures = RuntimeContract::ensures(|i: &i32| *i > 0, "must be positive");
+  let check = RuntimeContract::check(|i: &i32| *i > 0, "must be positive");
+
+  assert!(matches!(
+    requires.verify(&0),
+    Err(RuntimeContractError::RequiresFailure(_))
+  ));
+  assert!(matches!(
+    ensures.verify(&0),
+    Err(RuntimeContractError::EnsuresFailure(_))
+  ));
+  assert!(matches!(
+    check.verify(&0),
+    Err(RuntimeContractError::CheckFailure(_))
+  ));
+
+  let err = check.verify(&0).unwrap_err();
+
+  assert_eq!(err.kind(), ContractKind::Check);
+  assert_eq!(err.message(), "must be positive");
+  assert_eq!(
+    err.to_string(),
+    format!(
+      "check validation failed: invariant violated: must be positive at {}",
+      err.location().unwrap()
+    )
+  );
+}
+
+#[test]
+fn contract_is_reusable_across_values() {
+  let contract = RuntimeContract::new(ContractKind::Check, |s: &str| !s.is_empty(), "non-empty");
+  let cloned = contract.clone();
+
+  assert!(contract.verify("hello").is_ok());
+  assert!(cloned.verify("").is_err());
+  assert_eq!(cloned.kind(), ContractKind::Check);
+  assert_eq!(cloned.message(), "non-empty");
+}
+
+#[test]
+fn contract_apply_yields_value() {
+  let contract = RuntimeContract::ensures(|v: &Vec<u8>| v.len() == 2, "two elements");
+
+  assert_eq!(contract.apply(vec![1, 2]), Ok(vec![1, 2]));
+  assert!(contract.apply(vec![1]).is_err());
+}
+
+#[test]
+fn contract_converts_into_function() {
+  let contract_fn = RuntimeContract::requires(|i: &u8| *i < 10, "single digit").into_fn();
+
+  assert_eq!(contract_fn(9), Ok(9));
+  assert!(contract_fn(10).is_err());
+}
+
+#[test]
+fn contract_and_reports_violated_sub_contract() {
+  let positive = RuntimeContract::requires(|i: &i32| *i > 0, "positive");
+  let even = RuntimeContract::requires(|i: &i32| i % 2 == 0, "even");
+  let both = positive.and(even);
+
+  assert_eq!(both.verify(&2), Ok(()));
+  let err = both.verify(&-2).unwrap_err();
+
+  assert_eq!(err.kind(), ContractKind::Requires);
+  assert_eq!(err.message(), "positive and even (violated: positive)");
+}
+
+#[test]
+fn contract_or_holds_when_either_holds() {
+  let small = RuntimeContract::check(|i: &i32| *i < 10, "small");
+  let large = RuntimeContract::check(|i: &i32| *i > 100, "large");
+  let either = small.or(large);
+
+  assert!(either.verify(&5).is_ok());
+  assert!(either.verify(&500).is_ok());
+  let err = either.verify(&50).unwrap_err();
+
+  assert_eq!(err.kind(), ContractKind::Check);
+  assert_eq!(err.message(), "small or large");
+}
+
+#[test]
+fn contract_not_inverts_contract() {
+  let empty = RuntimeContract::requires(|s: &str| s.is_empty(), "empty").not();
+
+  assert!(empty.verify("hello").is_ok());
+  let err = empty.verify("").unwrap_err();
+
+  assert_eq!(err.kind(), ContractKind::Requires);
+  assert_eq!(err.message(), "not (empty)");
+}
+
+#[test]
+fn contract_implies_only_checks_consequent_when_antecedent_holds() {
+  let negative = RuntimeContract::requires(|i: &i32| *i < 0, "negative");
+  let even = RuntimeContract::requires(|i: &i32| i % 2 == 0, "even");
+  let negative_implies_even = negative.implies(even);
+
+  assert!(negative_implies_even.verify(&3).is_ok());
+  assert!(negative_implies_even.verify(&-4).is_ok());
+  let err = negative_implies_even.verify(&-3).unwrap_err();
+
+  assert_eq!(err.kind(), ContractKind::Requires);
+  assert_eq!(err.message(), "negative implies even (violated: even)");
+}
+
+#[test]
+fn contract_all_of_and_any_of_fold_contracts() {
+  let bounds = || {
+    vec![
+      RuntimeContract::ensures(|i: &u32| *i > 1, "above one"),
+      RuntimeContract::ensures(|i: &u32| *i < 5, "below five"),
+    ]
+  };
+
+  let all = RuntimeContract::all_of(bounds());
+  let any = RuntimeContract::any_of(bounds());
+
+  assert!(all.verify(&3).is_ok());
+  let err = all.verify(&7).unwrap_err();
+
+  assert_eq!(err.kind(), ContractKind::Ensures);
+  assert_eq!(
+    err.message(),
+    "above one and below five (violated: below five)"
+  );
+  assert!(any.verify(&7).is_ok());
+  assert!(RuntimeContract::<u32>::all_of(Vec::new())
+    .verify(&0)
+    .is_ok());
+  assert!(RuntimeContract::<u32>::any_of(Vec::new())
+    .verify(&0)
+    .is_err());
+}
+
+#[test]
+fn contract_always_is_identity_of_and() {
+  let positive = RuntimeContract::requires(|i: &i32| *i > 0, "positive");
+  let combined = RuntimeContract::always()
+    .and(positive)
+    .with_message("positive");
+
+  assert!(combined.verify(&1).is_ok());
+  let err = combined.verify(&0).unwrap_err();
+
+  assert_eq!(err.kind(), ContractKind::Check);
+  assert_eq!(err.message(), "positive");
+}
+
+#[test]
+fn contract_records_caller_location() {
+  let contract = RuntimeContract::requires(|i: &i32| *i > 0, "positive");
+  let line = line!() + 1;
+  let err = contract.verify(&0).unwrap_err();
+  let location = err.location().unwrap();
+
+  assert_eq!(location.file(), file!());
+  assert_eq!(location.line(), line);
+  assert_eq!(location.module_path(), None);
+}
@@ -0,0 +1,137 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use pretty_assertions::assert_eq;
+
+use runtime_contracts::cost::{self, Cost, Gate};
+use runtime_contracts::{check, ensures, requires, RuntimeContract};
+
+fn with_gate(cost: Cost, gate: Gate, test: impl FnOnce()) {
+  let _guard = common::serial();
+
+  cost::reset_skipped();
+  cost::set_gate(cost, gate);
+  test();
+  cost::set_gate(cost, Gate::Always);
+}
+
+fn counting_contract(cost: Cost) -> (RuntimeContract<u32>, Arc<AtomicUsize>) {
+  let evaluations = Arc::new(AtomicUsize::new(0));
+  let counter = Arc::clone(&evaluations);
+  let contract = RuntimeContract::check(
+    move |_: &u32| {
+      counter.fetch_add(1, Ordering::Relaxed);
+      false
+    },
+    "always fails",
+  )
+  .with_cost(cost);
+
+  (contract, evaluations)
+}
+
+#[test]
+fn every_class_runs_by_default() {
+  let _guard = common::serial();
+
+  for cost in [Cost::Cheap, Cost::Moderate, Cost::Expensive] {
+    assert_eq!(cost::gate(cost), Gate::Always);
+  }
+
+  assert!(check(|| false, "cheap contracts always run").is_err());
+}
+
+#[test]
+fn never_gate_skips_and_counts() {
+  with_gate(Cost::Expensive, Gate::Never, || {
+    let (contract, evaluations) = counting_contract(Cost::Expensive);
+
+    for _ in 0..5 {
+      assert_eq!(contract.verify(&1), Ok(()));
+    }
+
+    assert_eq!(evaluations.load(Ordering::Relaxed), 0);
+    assert_eq!(cost::skipped(Cost::Expensive), 5);
+    assert_eq!(cost::skipped(Cost::Cheap), 0);
+  });
+}
+
+#[test]
+fn every_nth_gate_runs_periodically() {
+  with_gate(Cost::Moderate, Gate::EveryNth(3), || {
+    let (contract, evaluations) = counting_contract(Cost::Moderate);
+    let failures = (0..9).filter(|_| contract.verify(&1).is_err()).count();
+
+    assert_eq!(failures, 3);
+    assert_eq!(evaluations.load(Ordering::Relaxed), 3);
+    assert_eq!(cost::skipped(Cost::Moderate), 6);
+  });
+}
+
+#[test]
+fn every_nth_gate_counts_each_call_site_separately() {
+  with_gate(Cost::Expensive, Gate::EveryNth(2), || {
+    let (first, first_evaluations) = counting_contract(Cost::Expensive);
+    let (second, second_evaluations) = counting_contract(Cost::Expensive);
+
+    for _ in 0..10 {
+      let _ = first.verify(&1);
+      let _ = second.verify(&1);
+    }
+
+    assert_eq!(first_evaluations.load(Ordering::Relaxed), 5);
+    assert_eq!(second_evaluations.load(Ordering::Relaxed), 5);
+    assert_eq!(cost::skipped(Cost::Expensive), 10);
+  });
+}
+
+#[test]
+fn sample_gate_runs_a_fraction() {
+  with_gate(Cost::Expensive, Gate::Sample(0.25), || {
+    let (contract, evaluations) = counting_contract(Cost::Expensive);
+
+    for _ in 0..4000 {
+      let _ = contract.verify(&1);
+    }
+
+    let evaluated = evaluations.load(Ordering::Relaxed);
+
+    assert!(
+      (700..1300).contains(&evaluated),
+      "evaluated {evaluated} times"
+    );
+    assert_eq!(evaluated as u64 + cost::skipped(Cost::Expensive), 4000);
+  });
+}
+
+#[test]
+fn combined_contracts_take_highest_cost() {
+  let cheap = RuntimeContract::requires(|_: &u8| true, "cheap");
+  let expensive = RuntimeContract::requires(|_: &u8| true, "expensive").with_cost(Cost::Expensive);
+
+  assert_eq!(cheap.clone().and(expensive).cost(), Cost::Expensive);
+  assert_eq!(cheap.not().cost(), Cost::Cheap);
+}
+
+#[test]
+fn macros_accept_a_cost() {
+  with_gate(Cost::Expensive, Gate::Never, || {
+    assert_eq!(requires!(1 > 2, cost = Cost::Expensive), Ok(()));
+    assert_eq!(
+      ensures!(1, |i| *i > 2, "must exceed two", cost = Cost::Expensive),
+      Ok(1)
+    );
+    assert_eq!(
+      check!(1 > 2, "must exceed two", cost = Cost::Expensive),
+      Ok(())
+    );
+    assert!(check!(1 > 2, "cheap contracts still run").is_err());
+    assert_eq!(cost::skipped(Cost::Expensive), 3);
+  });
+
+  let err = requires!(1 > 2, cost = Cost::Expensive).unwrap_err();
+
+  assert_eq!(err.message(), "1 > 2");
+}
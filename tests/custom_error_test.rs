@@ -0,0 +1,58 @@
+use pretty_assertions::assert_eq;
+
+use runtime_contracts::{check_or, ensures_or, requires_or};
+
+#[derive(Debug, PartialEq)]
+enum AppError {
+  BadInput,
+  BadOutput(u32),
+  Corrupted,
+}
+
+impl std::fmt::Display for AppError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{self:?}")
+  }
+}
+
+#[test]
+fn requires_or_yields_custom_error() {
+  assert_eq!(requires_or(|| true, || AppError::BadInput), Ok(()));
+  assert_eq!(
+    requires_or(|| false, || AppError::BadInput),
+    Err(AppError::BadInput)
+  );
+}
+
+#[test]
+fn ensures_or_yields_value_or_custom_error() {
+  assert_eq!(
+    ensures_or(4, |v| v % 2 == 0, || AppError::BadOutput(4)),
+    Ok(4)
+  );
+  assert_eq!(
+    ensures_or(5, |v| v % 2 == 0, || AppError::BadOutput(5)),
+    Err(AppError::BadOutput(5))
+  );
+}
+
+#[test]
+fn check_or_only_builds_error_on_failure() {
+  let mut built = false;
+
+  assert_eq!(
+    check_or(
+      || true,
+      || {
+        built = true;
+        AppError::Corrupted
+      }
+    ),
+    Ok(())
+  );
+  assert!(!built);
+  assert_eq!(
+    check_or(|| false, || AppError::Corrupted),
+    Err(AppError::Corrupted)
+  );
+}
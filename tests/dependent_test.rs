@@ -0,0 +1,126 @@
+use pretty_assertions::assert_eq;
+
+use runtime_contracts::blame::Party;
+use runtime_contracts::function::{DependentContract, DEPENDENCY};
+use runtime_contracts::{context, ContractKind};
+
+fn dependency(err: &runtime_contracts::error::RuntimeContractError) -> Option<&str> {
+  err
+    .failure()
+    .context()
+    .iter()
+    .rev()
+    .find(|(key, _)| key == DEPENDENCY)
+    .map(|(_, value)| value.as_str())
+}
+
+fn sorting() -> DependentContract<[i32], Vec<i32>> {
+  DependentContract::new()
+    .requires("short", |xs: &[i32]| xs.len() < 5, "at most four elements")
+    .ensures(
+      "same length",
+      |xs: &[i32], ys: &Vec<i32>| xs.len() == ys.len(),
+      "length must be preserved",
+    )
+    .ensures(
+      "sorted",
+      |_: &[i32], ys: &Vec<i32>| ys.windows(2).all(|w| w[0] <= w[1]),
+      "result must be sorted",
+    )
+}
+
+#[test]
+fn postconditions_see_the_arguments_and_the_result() {
+  let sort = sorting().wrap(|xs: &[i32]| {
+    let mut ys = xs.to_vec();
+    ys.sort();
+    ys
+  });
+
+  assert_eq!(sort(&[3, 1, 2]), Ok(vec![1, 2, 3]));
+}
+
+#[test]
+fn violations_name_the_dependency_at_fault() {
+  let truncate = sorting().wrap(|xs: &[i32]| xs.iter().copied().take(2).collect());
+  let reverse = sorting().wrap(|xs: &[i32]| xs.iter().rev().copied().collect());
+
+  let too_long = truncate(&[1, 2, 3, 4, 5]).unwrap_err();
+  let truncated = truncate(&[1, 2, 3]).unwrap_err();
+  let reversed = reverse(&[1, 2, 3]).unwrap_err();
+
+  assert_eq!(
+    (too_long.kind(), dependency(&too_long)),
+    (ContractKind::Requires, Some("short"))
+  );
+  assert_eq!(
+    (truncated.kind(), dependency(&truncated)),
+    (ContractKind::Ensures, Some("same length"))
+  );
+  assert_eq!(
+    (reversed.kind(), dependency(&reversed)),
+    (ContractKind::Ensures, Some("sorted"))
+  );
+  assert_eq!(reversed.message(), "result must be sorted");
+}
+
+#[test]
+fn blame_follows_the_flow_of_values() {
+  let identity = sorting().wrap(|xs: &[i32]| xs.to_vec());
+  let empty = sorting().wrap(|_: &[i32]| Vec::new());
+
+  assert_eq!(
+    identity(&[1; 5]).unwrap_err().blame().party(),
+    Party::Caller
+  );
+  assert_eq!(empty(&[1]).unwrap_err().blame().party(), Party::Callee);
+}
+
+#[test]
+fn rejected_arguments_never_reach_the_function() {
+  let sort = sorting().wrap(|_: &[i32]| -> Vec<i32> { panic!("must not be called") });
+
+  assert!(sort(&[1; 5]).is_err());
+}
+
+#[test]
+fn surrounding_context_is_kept() {
+  let _request = context::scope("request_id", 7);
+  let reverse = sorting().wrap(|xs: &[i32]| xs.iter().rev().copied().collect());
+
+  let err = reverse(&[1, 2]).unwrap_err();
+
+  assert_eq!(
+    err.failure().context(),
+    &[
+      ("request_id".to_string(), "7".to_string()),
+      (DEPENDENCY.to_string(), "sorted".to_string()),
+    ]
+  );
+  assert_eq!(
+    context::current(),
+    vec![("request_id".to_string(), "7".to_string())]
+  );
+}
+
+#[test]
+fn functions_of_several_arguments_borrow_a_tuple() {
+  let pad = DependentContract::new()
+    .ensures(
+      "padded",
+      |(s, width): &(String, usize), padded: &String| padded.len() == s.len().max(*width),
+      "must be padded to the width",
+    )
+    .wrap(|(s, width): &(String, usize)| format!("{s:<width$}"));
+
+  assert_eq!(pad(&("ab".to_string(), 4)), Ok("ab  ".to_string()));
+}
+
+#[test]
+fn lists_the_names_of_its_parts() {
+  assert_eq!(
+    sorting().names().collect::<Vec<_>>(),
+    vec!["short", "same length", "sorted"]
+  );
+  assert_eq!(DependentContract::<i32, i32>::default().names().count(), 0);
+}
@@ -0,0 +1,102 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use pretty_assertions::assert_eq;
+
+use runtime_contracts::blame::Party;
+use runtime_contracts::{contract_fn, ContractKind, RuntimeContract, RuntimeContractFunction};
+
+fn non_empty() -> RuntimeContract<String> {
+  RuntimeContract::requires(|s: &String| !s.is_empty(), "must not be empty")
+}
+
+#[test]
+fn checks_arguments_and_results_on_every_call() {
+  let shout = contract_fn(
+    |s: String| s.to_uppercase(),
+    non_empty(),
+    RuntimeContract::ensures(|s: &String| s.ends_with('!'), "must end with a bang"),
+  );
+
+  assert_eq!(shout("hey!".to_string()), Ok("HEY!".to_string()));
+
+  let bad_argument = shout(String::new()).unwrap_err();
+  let bad_result = shout("hey".to_string()).unwrap_err();
+
+  assert_eq!(bad_argument.kind(), ContractKind::Requires);
+  assert_eq!(bad_argument.message(), "must not be empty");
+  assert_eq!(bad_argument.blame().party(), Party::Caller);
+  assert_eq!(bad_result.kind(), ContractKind::Ensures);
+  assert_eq!(bad_result.message(), "must end with a bang");
+  assert_eq!(bad_result.blame().party(), Party::Callee);
+}
+
+#[test]
+fn rejected_arguments_never_reach_the_function() {
+  let calls = Arc::new(AtomicUsize::new(0));
+  let counter = Arc::clone(&calls);
+  let guarded = contract_fn(
+    move |s: String| {
+      counter.fetch_add(1, Ordering::Relaxed);
+      s
+    },
+    non_empty(),
+    RuntimeContract::always(),
+  );
+
+  assert!(guarded(String::new()).is_err());
+  assert_eq!(calls.load(Ordering::Relaxed), 0);
+  assert!(guarded("ok".to_string()).is_ok());
+  assert_eq!(calls.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn blame_does_not_depend_on_the_kinds_of_the_contracts() {
+  let contract = || RuntimeContract::check(|i: &i32| *i > 0, "must be positive");
+  let negate = contract_fn(
+    |i: i32| -i,
+    contract().with_blame("client"),
+    contract().with_blame("negate"),
+  );
+
+  let argument = negate(-1).unwrap_err();
+  let result = negate(1).unwrap_err();
+
+  assert_eq!(argument.kind(), ContractKind::Requires);
+  assert_eq!(argument.blame().to_string(), "client");
+  assert_eq!(result.kind(), ContractKind::Ensures);
+  assert_eq!(result.blame().to_string(), "negate");
+}
+
+#[test]
+fn violations_are_located_where_the_function_was_wrapped() {
+  let line = line!() + 1;
+  let guarded = contract_fn(|s: String| s, non_empty(), RuntimeContract::always());
+  let err = guarded(String::new()).unwrap_err();
+
+  assert_eq!(err.location().unwrap().line(), line);
+}
+
+#[test]
+fn functions_of_several_arguments_take_tuples() {
+  let divide = contract_fn(
+    |(a, b): (i32, i32)| a / b,
+    RuntimeContract::requires(|(_, b): &(i32, i32)| *b != 0, "divisor must not be zero"),
+    RuntimeContract::always(),
+  );
+
+  assert_eq!(divide((6, 3)), Ok(2));
+  assert!(divide((6, 0)).is_err());
+}
+
+#[test]
+fn wrapped_functions_are_runtime_contract_functions() {
+  let trim: Box<RuntimeContractFunction<String>> = Box::new(contract_fn(
+    |s: String| s.trim().to_string(),
+    RuntimeContract::always(),
+    non_empty(),
+  ));
+
+  assert_eq!(trim(" a ".to_string()), Ok("a".to_string()));
+  assert!(trim("   ".to_string()).is_err());
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use pretty_assertions::assert_eq;
+
+use runtime_contracts::error::RuntimeContractError;
+use runtime_contracts::hooks;
+use runtime_contracts::{check, ensures, requires, ContractKind};
+
+type Seen = Arc<Mutex<Vec<(ContractKind, String, u32)>>>;
+
+fn recording_hook() -> (Seen, impl Fn(&RuntimeContractError)) {
+  let seen: Seen = Arc::default();
+  let recorder = Arc::clone(&seen);
+
+  let hook = move |err: &RuntimeContractError| {
+    let line = err
+      .location()
+      .map(|location| location.line())
+      .unwrap_or_default();
+
+    recorder
+      .lock()
+      .unwrap()
+      .push((err.kind(), err.message().to_string(), line));
+  };
+
+  (seen, hook)
+}
+
+#[test]
+fn hooks_see_every_violation() {
+  let _guard = common::serial();
+  let (seen, hook) = recording_hook();
+
+  hooks::set_violation_hook(hook);
+
+  let line = line!() + 1;
+  let _ = requires(|| false, "first");
+  let _ = ensures(1, |v| *v == 1, "passes");
+  let _ = check(|| false, "second");
+
+  hooks::clear_violation_hooks();
+
+  assert_eq!(
+    *seen.lock().unwrap(),
+    vec![
+      (ContractKind::Requires, "first".to_string(), line),
+      (ContractKind::Check, "second".to_string(), line + 2),
+    ]
+  );
+}
+
+#[test]
+fn multiple_hooks_are_invoked_until_removed() {
+  let _guard = common::serial();
+  let (first_seen, first) = recording_hook();
+  let (second_seen, second) = recording_hook();
+
+  hooks::clear_violation_hooks();
+
+  let first_id = hooks::add_violation_hook(first);
+  hooks::add_violation_hook(second);
+
+  let _ = requires(|| false, "both");
+
+  assert!(hooks::remove_violation_hook(first_id));
+  assert!(!hooks::remove_violation_hook(first_id));
+
+  let _ = requires(|| false, "second only");
+
+  hooks::clear_violation_hooks();
+
+  assert_eq!(first_seen.lock().unwrap().len(), 1);
+  assert_eq!(second_seen.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn hooks_run_for_violations_on_other_threads() {
+  let _guard = common::serial();
+  let (seen, hook) = recording_hook();
+
+  hooks::set_violation_hook(hook);
+
+  std::thread::scope(|scope| {
+    for _ in 0..4 {
+      scope.spawn(|| {
+        let _ = check(|| false, "threaded");
+      });
+    }
+  });
+
+  hooks::clear_violation_hooks();
+
+  assert_eq!(seen.lock().unwrap().len(), 4);
+}
@@ -0,0 +1,168 @@
+use pretty_assertions::assert_eq;
+
+use runtime_contracts::error::RuntimeContractError;
+use runtime_contracts::iter::{IteratorContracts, INDEX};
+use runtime_contracts::{context, ContractKind};
+
+fn index(err: &RuntimeContractError) -> Option<&str> {
+  err
+    .failure()
+    .context()
+    .iter()
+    .rev()
+    .find(|(key, _)| key == INDEX)
+    .map(|(_, value)| value.as_str())
+}
+
+#[test]
+fn elements_which_hold_pass_through() {
+  let doubled: Result<Vec<_>, _> = (1..=3)
+    .requires_each(|i| *i > 0, "must be positive")
+    .map(|i| i.map(|i| i * 2))
+    .collect();
+
+  assert_eq!(doubled, Ok(vec![2, 4, 6]));
+}
+
+#[test]
+fn each_adapter_produces_errors_of_its_kind() {
+  let kind = |results: Vec<Result<i32, RuntimeContractError>>| {
+    results.into_iter().find_map(Result::err).unwrap().kind()
+  };
+
+  assert_eq!(
+    kind([1, -1].into_iter().requires_each(|i| *i > 0, "").collect()),
+    ContractKind::Requires
+  );
+  assert_eq!(
+    kind([1, -1].into_iter().ensures_each(|i| *i > 0, "").collect()),
+    ContractKind::Ensures
+  );
+  assert_eq!(
+    kind([1, -1].into_iter().check_each(|i| *i > 0, "").collect()),
+    ContractKind::Check
+  );
+}
+
+#[test]
+fn the_first_violation_stops_the_iterator() {
+  let mut evaluated = Vec::new();
+  let results: Vec<_> = [1, -2, 3, -4]
+    .into_iter()
+    .inspect(|i| evaluated.push(*i))
+    .requires_each(|i| *i > 0, "must be positive")
+    .collect();
+
+  assert_eq!(results.len(), 2);
+  assert_eq!(results[0], Ok(1));
+  assert_eq!(
+    results[1].as_ref().unwrap_err().message(),
+    "must be positive"
+  );
+  assert_eq!(index(results[1].as_ref().unwrap_err()), Some("1"));
+  assert_eq!(evaluated, vec![1, -2]);
+}
+
+#[test]
+fn violations_are_located_where_the_adapter_was_applied() {
+  let strings = ["ok", ""].into_iter();
+  let line = line!() + 1;
+  let mut checked = strings.check_each(|s| !s.is_empty(), "must not be empty");
+
+  let err = checked.nth(1).unwrap().unwrap_err();
+
+  assert_eq!(err.location().unwrap().line(), line);
+}
+
+#[test]
+fn surrounding_context_is_kept() {
+  let _batch = context::scope("batch", "b-17");
+  let err = [0]
+    .into_iter()
+    .requires_each(|i| *i > 0, "must be positive")
+    .next()
+    .unwrap()
+    .unwrap_err();
+
+  assert_eq!(
+    err.failure().context(),
+    &[
+      ("batch".to_string(), "b-17".to_string()),
+      (INDEX.to_string(), "0".to_string())
+    ]
+  );
+  assert_eq!(
+    context::current(),
+    vec![("batch".to_string(), "b-17".to_string())]
+  );
+}
+
+#[test]
+fn sorted_elements_pass_through() {
+  let sorted: Result<Vec<_>, _> = [1, 1, 2, 5].into_iter().check_sorted().collect();
+
+  assert_eq!(sorted, Ok(vec![1, 1, 2, 5]));
+}
+
+#[test]
+fn unsorted_elements_are_violations_at_the_element_out_of_order() {
+  let err = [1, 3, 2, 4]
+    .into_iter()
+    .check_sorted()
+    .find_map(Result::err)
+    .unwrap();
+
+  assert_eq!(err.kind(), ContractKind::Check);
+  assert_eq!(err.message(), "elements must be sorted");
+  assert_eq!(index(&err), Some("2"));
+}
+
+#[test]
+fn incomparable_elements_are_not_sorted() {
+  let err = [1.0, f64::NAN]
+    .into_iter()
+    .check_sorted()
+    .find_map(Result::err)
+    .unwrap();
+
+  assert_eq!(index(&err), Some("1"));
+}
+
+#[test]
+fn lengths_within_bounds_pass_through() {
+  let results: Result<Vec<_>, _> = (0..3).check_len_between(3, 3).collect();
+
+  assert_eq!(results, Ok(vec![0, 1, 2]));
+  assert_eq!((0..0).check_len_between(0, 2).count(), 0);
+}
+
+#[test]
+fn iterators_which_are_too_long_stop_at_the_first_element_past_the_maximum() {
+  let mut evaluated = 0;
+  let results: Vec<_> = (0..10)
+    .inspect(|_| evaluated += 1)
+    .check_len_between(1, 2)
+    .collect();
+
+  assert_eq!(results.len(), 3);
+  assert_eq!(
+    results[2].as_ref().unwrap_err().message(),
+    "length must be between 1 and 2"
+  );
+  assert_eq!(index(results[2].as_ref().unwrap_err()), Some("2"));
+  assert_eq!(evaluated, 3);
+}
+
+#[test]
+fn iterators_which_are_too_short_end_with_an_error() {
+  let mut checked = (0..2).check_len_between(3, 5);
+
+  assert_eq!(checked.next(), Some(Ok(0)));
+  assert_eq!(checked.next(), Some(Ok(1)));
+
+  let err = checked.next().unwrap().unwrap_err();
+
+  assert_eq!(err.message(), "length must be between 3 and 5");
+  assert_eq!(index(&err), Some("2"));
+  assert_eq!(checked.next(), None);
+}
@@ -0,0 +1,178 @@
+#![cfg(feature = "journal")]
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use pretty_assertions::assert_eq;
+
+use runtime_contracts::journal::{Journal, Record};
+use runtime_contracts::{check, context, requires, try_requires, ContractKind};
+
+fn records(path: &Path) -> Vec<Record> {
+  fs::read_to_string(path)
+    .unwrap()
+    .lines()
+    .map(|line| serde_json::from_str(line).unwrap())
+    .collect()
+}
+
+#[test]
+fn installed_journal_records_violations() {
+  let _guard = common::serial();
+  let dir = tempfile::tempdir().unwrap();
+  let path = dir.path().join("violations.jsonl");
+
+  let journal = Journal::open(&path).unwrap().install();
+
+  {
+    let _request = context::scope("request_id", "7f3a");
+    let _ = requires!(1 > 2, "one must exceed two");
+  }
+  let line = line!() - 2;
+  let _ = check(|| true, "holds, so it is not recorded");
+
+  drop(journal);
+
+  let records = records(&path);
+
+  assert_eq!(records.len(), 1);
+
+  let record = &records[0];
+  let location = record.location().unwrap();
+
+  assert_eq!(record.kind(), ContractKind::Requires);
+  assert_eq!(record.message(), "one must exceed two");
+  assert_eq!(record.predicate(), Some("1 > 2"));
+  assert_eq!(location.file(), file!());
+  assert_eq!(location.line(), line);
+  assert_eq!(location.module_path(), Some("journal_test"));
+  assert_eq!(
+    record.context().get("request_id").map(String::as_str),
+    Some("7f3a")
+  );
+  assert_eq!(
+    record.thread(),
+    Some("installed_journal_records_violations")
+  );
+}
+
+#[test]
+fn records_are_buffered_until_flushed() {
+  let _guard = common::serial();
+  let dir = tempfile::tempdir().unwrap();
+  let path = dir.path().join("violations.jsonl");
+  let journal = Journal::open(&path).unwrap();
+
+  journal
+    .record(&check(|| false, "buffered").unwrap_err())
+    .unwrap();
+
+  assert_eq!(fs::read_to_string(&path).unwrap(), "");
+
+  journal.flush().unwrap();
+  assert_eq!(records(&path).len(), 1);
+
+  journal
+    .record(&check(|| false, "flushed on drop").unwrap_err())
+    .unwrap();
+  drop(journal);
+
+  let messages: Vec<_> = records(&path)
+    .iter()
+    .map(|record| record.message().to_string())
+    .collect();
+
+  assert_eq!(messages, vec!["buffered", "flushed on drop"]);
+}
+
+#[test]
+fn appends_to_existing_journals() {
+  let _guard = common::serial();
+  let dir = tempfile::tempdir().unwrap();
+  let path = dir.path().join("violations.jsonl");
+  let error = check(|| false, "appended").unwrap_err();
+
+  for _ in 0..2 {
+    Journal::open(&path).unwrap().record(&error).unwrap();
+  }
+
+  assert_eq!(records(&path).len(), 2);
+}
+
+#[test]
+fn rotates_by_size() {
+  let _guard = common::serial();
+  let dir = tempfile::tempdir().unwrap();
+  let path = dir.path().join("violations.jsonl");
+  let error = check(|| false, "rotated").unwrap_err();
+  let record_len = {
+    let probe = dir.path().join("probe.jsonl");
+    Journal::open(&probe).unwrap().record(&error).unwrap();
+
+    fs::metadata(&probe).unwrap().len()
+  };
+
+  // Room for two records per file, keeping two rotated files.
+  let journal = Journal::open(&path)
+    .unwrap()
+    .with_max_size(record_len * 2 + record_len / 2)
+    .with_max_files(2);
+
+  for _ in 0..7 {
+    journal.record(&error).unwrap();
+  }
+  drop(journal);
+
+  let count = |path: &Path| records(path).len();
+
+  assert_eq!(count(&path), 1);
+  assert_eq!(count(&dir.path().join("violations.jsonl.1")), 2);
+  assert_eq!(count(&dir.path().join("violations.jsonl.2")), 2);
+  assert!(!dir.path().join("violations.jsonl.3").exists());
+}
+
+#[test]
+fn records_round_trip_through_json() {
+  let _guard = common::serial();
+  let error = requires(|| false, "round trip").unwrap_err();
+  let record = Record::new(&error);
+  let json = serde_json::to_string(&record).unwrap();
+
+  assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), record);
+  assert!(json.contains(r#""kind":"requires""#));
+
+  let timestamp = record.timestamp();
+
+  assert_eq!(timestamp.len(), "2024-01-31T12:34:56.789Z".len());
+  assert!(timestamp.starts_with("20") && timestamp.ends_with('Z'));
+}
+
+#[test]
+fn records_the_cause_of_unevaluated_contracts() {
+  let _guard = common::serial();
+  let violated = Record::new(&requires(|| false, "violated").unwrap_err());
+  let unevaluated =
+    Record::new(&try_requires(|| "x".parse::<u8>().map(|n| n > 0), "unevaluated").unwrap_err());
+
+  assert_eq!(violated.cause(), None);
+  assert_eq!(unevaluated.cause(), Some("invalid digit found in string"));
+  assert!(serde_json::to_string(&unevaluated)
+    .unwrap()
+    .contains(r#""cause":"invalid digit found in string""#));
+}
+
+#[test]
+fn records_from_unnamed_threads_omit_the_thread() {
+  let _guard = common::serial();
+  let error = check(|| false, "unnamed").unwrap_err();
+  let record = std::thread::spawn(move || Record::new(&error))
+    .join()
+    .unwrap();
+  let json = serde_json::to_string(&record).unwrap();
+
+  assert_eq!(record.thread(), None);
+  assert!(!json.contains("thread"));
+  assert_eq!(serde_json::from_str::<Record>(&json).unwrap(), record);
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use runtime_contracts::policy::{self, AssertionLevel, LEVEL_ENV_VAR};
+use runtime_contracts::{check, ensures, requires, ContractKind};
+
+fn with_level(level: AssertionLevel, test: impl FnOnce()) {
+  let _guard = common::serial();
+
+  policy::set_level(level);
+  test();
+  policy::set_level(AssertionLevel::All);
+}
+
+#[test]
+fn levels_are_cumulative() {
+  assert!(!AssertionLevel::None.enables(ContractKind::Requires));
+  assert!(AssertionLevel::Requires.enables(ContractKind::Requires));
+  assert!(!AssertionLevel::Requires.enables(ContractKind::Ensures));
+  assert!(AssertionLevel::Ensures.enables(ContractKind::Ensures));
+  assert!(!AssertionLevel::Ensures.enables(ContractKind::Check));
+  assert!(AssertionLevel::All.enables(ContractKind::Check));
+}
+
+#[test]
+fn every_contract_is_evaluated_by_default() {
+  with_level(AssertionLevel::default(), || {
+    assert!(requires(|| false, "fails").is_err());
+    assert!(ensures(1, |v| *v == 2, "fails").is_err());
+    assert!(check(|| false, "fails").is_err());
+  });
+}
+
+#[test]
+fn requires_level_skips_postconditions_and_invariants() {
+  with_level(AssertionLevel::Requires, || {
+    assert!(requires(|| false, "fails").is_err());
+    assert_eq!(ensures(1, |v| *v == 2, "skipped"), Ok(1));
+    assert_eq!(check(|| false, "skipped"), Ok(()));
+  });
+}
+
+#[test]
+fn none_level_skips_everything() {
+  with_level(AssertionLevel::None, || {
+    assert_eq!(requires(|| false, "skipped"), Ok(()));
+  });
+}
+
+#[test]
+fn level_is_read_from_environment() {
+  let _guard = common::serial();
+
+  std::env::set_var(LEVEL_ENV_VAR, "ensures");
+  policy::reload_from_env();
+  std::env::remove_var(LEVEL_ENV_VAR);
+
+  assert_eq!(policy::level(), AssertionLevel::Ensures);
+
+  policy::reload_from_env();
+
+  assert_eq!(policy::level(), AssertionLevel::All);
+}
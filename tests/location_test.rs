@@ -0,0 +1,63 @@
+use pretty_assertions::assert_eq;
+
+use runtime_contracts::{check, ensures, requires, ContractKind};
+
+#[test]
+fn functions_record_caller_location() {
+  let line = line!() + 1;
+  let err = requires(|| false, "should always fail").unwrap_err();
+  let location = err.location().unwrap();
+
+  assert_eq!(location.file(), file!());
+  assert_eq!(location.line(), line);
+  assert_eq!(location.column(), 13);
+  assert_eq!(err.predicate(), None);
+}
+
+#[test]
+fn macros_record_predicate_and_module() {
+  let i = 0;
+  let line = line!() + 1;
+  let err = requires!(i > 0, "i must be positive").unwrap_err();
+  let location = err.location().unwrap();
+
+  assert_eq!(err.kind(), ContractKind::Requires);
+  assert_eq!(err.message(), "i must be positive");
+  assert_eq!(err.predicate(), Some("i > 0"));
+  assert_eq!(location.file(), file!());
+  assert_eq!(location.line(), line);
+  assert_eq!(location.module_path(), Some(module_path!()));
+}
+
+#[test]
+fn macros_default_message_to_predicate() {
+  let err = check!(1 + 1 == 3).unwrap_err();
+
+  assert_eq!(err.kind(), ContractKind::Check);
+  assert_eq!(err.message(), "1 + 1 == 3");
+}
+
+#[test]
+fn ensures_macro_yields_value() {
+  assert_eq!(ensures!(5, |v| *v == 5), Ok(5));
+
+  let err = ensures!(5, |v| *v == 6, "must be six").unwrap_err();
+
+  assert_eq!(err.kind(), ContractKind::Ensures);
+  assert_eq!(err.predicate(), Some("|v| *v == 6"));
+}
+
+#[test]
+fn display_includes_predicate_and_location() {
+  let line = line!() + 1;
+  let err = check!(false, "never holds").unwrap_err();
+
+  assert_eq!(
+    err.to_string(),
+    format!(
+      "check validation failed: invariant violated: never holds (`false`) at {}:{line}:13 in {}",
+      file!(),
+      module_path!()
+    )
+  );
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "log")]
+
+mod common;
+
+use std::sync::{Mutex, Once};
+
+use log::{Level, Log, Metadata, Record};
+use pretty_assertions::assert_eq;
+
+use runtime_contracts::policy::{self, Policy};
+use runtime_contracts::{check, ensures, requires, ContractKind};
+
+/// A logger which records every record logged by this crate.
+struct Recorder {
+  records: Mutex<Vec<(Level, String, String)>>,
+}
+
+impl Log for Recorder {
+  fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+    metadata.target().starts_with("runtime_contracts")
+  }
+
+  fn log(&self, record: &Record<'_>) {
+    if self.enabled(record.metadata()) {
+      self.records.lock().unwrap().push((
+        record.level(),
+        record.target().to_string(),
+        record.args().to_string(),
+      ));
+    }
+  }
+
+  fn flush(&self) {}
+}
+
+static RECORDER: Recorder = Recorder {
+  records: Mutex::new(Vec::new()),
+};
+
+static INSTALL: Once = Once::new();
+
+fn take_records(test: impl FnOnce()) -> Vec<(Level, String, String)> {
+  let _guard = common::serial();
+
+  INSTALL.call_once(|| {
+    log::set_logger(&RECORDER).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+  });
+
+  RECORDER.records.lock().unwrap().clear();
+  test();
+
+  std::mem::take(&mut *RECORDER.records.lock().unwrap())
+}
+
+#[test]
+fn violations_are_logged_as_errors_with_kind_targets() {
+  let records = take_records(|| {
+    let _ = requires(|| true, "passes");
+    let _ = requires(|| false, "first");
+    let _ = check(|| false, "second");
+  });
+
+  let summary: Vec<_> = records
+    .iter()
+    .map(|(level, target, _)| (*level, target.as_str()))
+    .collect();
+
+  assert_eq!(
+    summary,
+    vec![
+      (Level::Error, "runtime_contracts::requires"),
+      (Level::Error, "runtime_contracts::check"),
+    ]
+  );
+  assert!(records[0]
+    .2
+    .starts_with("requires validation failed: first at "));
+}
+
+#[test]
+fn log_policy_logs_warnings_and_carries_on() {
+  let records = take_records(|| {
+    policy::set_kind_policy(ContractKind::Ensures, Policy::Log);
+
+    assert_eq!(ensures(1, |v| *v == 2, "carries on"), Ok(1));
+
+    policy::set_policy(Policy::Error);
+  });
+
+  assert_eq!(records.len(), 1);
+  assert_eq!(records[0].0, Level::Warn);
+  assert_eq!(
+    records[0].1,
+    runtime_contracts::logging::target(ContractKind::Ensures)
+  );
+}
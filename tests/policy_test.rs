@@ -0,0 +1,99 @@
+mod common;
+
+use pretty_assertions::assert_eq;
+
+use runtime_contracts::policy::{self, Policy, POLICY_ENV_VAR};
+use runtime_contracts::{check, ensures, requires, ContractKind};
+
+fn with_policy<F>(configure: F, test: impl FnOnce())
+where
+  F: FnOnce(),
+{
+  let _guard = common::serial();
+
+  configure();
+  test();
+  policy::set_policy(Policy::Error);
+}
+
+#[test]
+fn error_is_default_policy() {
+  with_policy(
+    || (),
+    || {
+      assert_eq!(policy::policy(ContractKind::Requires), Policy::Error);
+      assert!(requires(|| false, "should always fail").is_err());
+    },
+  );
+}
+
+#[test]
+fn log_policy_carries_on() {
+  with_policy(
+    || policy::set_policy(Policy::Log),
+    || {
+      assert_eq!(requires(|| false, "should be logged"), Ok(()));
+      assert_eq!(ensures(5, |v| *v == 6, "should be logged"), Ok(5));
+    },
+  );
+}
+
+#[test]
+fn disabled_policy_skips_evaluation() {
+  with_policy(
+    || policy::set_kind_policy(ContractKind::Check, Policy::Disabled),
+    || {
+      let mut evaluated = false;
+
+      let res = check(
+        || {
+          evaluated = true;
+          false
+        },
+        "should be skipped",
+      );
+
+      assert_eq!(res, Ok(()));
+      assert!(!evaluated);
+      assert!(requires(|| false, "still enforced").is_err());
+    },
+  );
+}
+
+#[test]
+fn panic_policy_panics() {
+  with_policy(
+    || policy::set_kind_policy(ContractKind::Ensures, Policy::Panic),
+    || {
+      let res = std::panic::catch_unwind(|| ensures(1, |v| *v == 2, "should panic"));
+      let payload = res.unwrap_err();
+      let message = payload.downcast_ref::<String>().unwrap();
+
+      assert!(message.starts_with("ensures validation failed: should panic"));
+    },
+  );
+}
+
+#[test]
+fn policy_is_read_from_environment() {
+  with_policy(
+    || {
+      std::env::set_var(POLICY_ENV_VAR, "log, requires=error, check=disabled, bogus");
+      policy::reload_from_env();
+      std::env::remove_var(POLICY_ENV_VAR);
+    },
+    || {
+      assert_eq!(policy::policy(ContractKind::Requires), Policy::Error);
+      assert_eq!(policy::policy(ContractKind::Ensures), Policy::Log);
+      assert_eq!(policy::policy(ContractKind::Check), Policy::Disabled);
+    },
+  );
+}
+
+#[test]
+fn policies_parse_from_strings() {
+  assert_eq!("Panic".parse::<Policy>(), Ok(Policy::Panic));
+  assert_eq!("off".parse::<Policy>(), Ok(Policy::Disabled));
+  assert!("sometimes".parse::<Policy>().is_err());
+  assert_eq!("ensures".parse::<ContractKind>(), Ok(ContractKind::Ensures));
+}
@@ -0,0 +1,113 @@
+#![cfg(feature = "problem")]
+
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+use runtime_contracts::error::RuntimeContractError;
+use runtime_contracts::problem::{ProblemDetails, ProblemMapping, CONTENT_TYPE};
+use runtime_contracts::{check, ensures, requires, try_requires, ContractKind};
+
+#[test]
+fn status_depends_on_who_is_at_fault() {
+  let statuses: Vec<_> = [
+    requires(|| false, "bad input").unwrap_err(),
+    ensures(1, |i| *i > 1, "bad output").unwrap_err(),
+    check(|| false, "bad state").unwrap_err(),
+  ]
+  .iter()
+  .map(|err| ProblemDetails::from(err).status())
+  .collect();
+
+  assert_eq!(statuses, vec![400, 500, 500]);
+}
+
+#[test]
+fn unevaluated_contracts_are_the_servers_fault() {
+  let err = try_requires(|| "".parse::<u8>().map(|n| n > 0), "bad input").unwrap_err();
+
+  let problem = ProblemDetails::from(&err);
+
+  assert_eq!(problem.status(), 500);
+  assert_eq!(
+    problem.type_uri(),
+    "urn:runtime-contracts:unevaluated:requires"
+  );
+  assert_eq!(problem.title(), "Precondition could not be evaluated");
+  assert_eq!(problem.detail(), "bad input");
+
+  let mapping = ProblemMapping::default()
+    .with_unevaluated_status(503)
+    .with_unevaluated_type_uri(
+      ContractKind::Requires,
+      "https://example.com/problems/unreadable-input",
+    );
+  let problem = mapping.problem(&err);
+
+  assert_eq!(mapping.unevaluated_status(), 503);
+  assert_eq!(problem.status(), 503);
+  assert_eq!(
+    problem.type_uri(),
+    "https://example.com/problems/unreadable-input"
+  );
+  assert_eq!(
+    mapping.type_uri(ContractKind::Requires),
+    "urn:runtime-contracts:violation:requires"
+  );
+}
+
+#[test]
+fn serializes_as_problem_json() {
+  let err = check(|| false, "balance must not be negative").unwrap_err();
+  let problem = ProblemDetails::from(err).with_instance("/accounts/7/withdrawals");
+
+  assert_eq!(CONTENT_TYPE, "application/problem+json");
+  assert_eq!(
+    serde_json::to_value(&problem).unwrap(),
+    json!({
+      "type": "urn:runtime-contracts:violation:check",
+      "title": "Invariant violated",
+      "status": 500,
+      "detail": "balance must not be negative",
+      "instance": "/accounts/7/withdrawals",
+      "kind": "check",
+    })
+  );
+}
+
+#[test]
+fn mapping_can_be_overridden() {
+  let mapping = ProblemMapping::default()
+    .with_status(ContractKind::Requires, 422)
+    .with_status(ContractKind::Check, 503)
+    .with_type_uri(
+      ContractKind::Requires,
+      "https://example.com/problems/invalid-input",
+    );
+  let err = RuntimeContractError::new(ContractKind::Requires, "amount must be positive");
+  let problem = mapping.problem(&err);
+
+  assert_eq!(problem.status(), 422);
+  assert_eq!(
+    problem.type_uri(),
+    "https://example.com/problems/invalid-input"
+  );
+  assert_eq!(problem.title(), "Precondition violated");
+  assert_eq!(problem.detail(), "amount must be positive");
+  assert_eq!(problem.instance(), None);
+  assert_eq!(mapping.status(ContractKind::Ensures), 500);
+  assert_eq!(mapping.status(ContractKind::Check), 503);
+}
+
+#[test]
+fn round_trips_through_json() {
+  let problem = ProblemDetails::from(RuntimeContractError::new(
+    ContractKind::Ensures,
+    "must be sorted",
+  ));
+  let json = serde_json::to_string(&problem).unwrap();
+
+  assert_eq!(
+    serde_json::from_str::<ProblemDetails>(&json).unwrap(),
+    problem
+  );
+}
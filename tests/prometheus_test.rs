@@ -0,0 +1,126 @@
+use pretty_assertions::assert_eq;
+
+use runtime_contracts::{check, prometheus, requires, stats, try_check};
+
+fn metric<'a>(rendered: &'a str, name: &str, message: &str) -> Vec<&'a str> {
+  rendered
+    .lines()
+    .filter(|line| line.starts_with(name) && line.contains(message))
+    .collect()
+}
+
+#[test]
+fn renders_evaluations_violations_and_skips() {
+  for i in 0..4 {
+    let _ = requires!(i < 3, "prometheus: i below three");
+  }
+
+  let rendered = prometheus::render(&stats::snapshot());
+  let labels = r#"{kind="requires",module="prometheus_test",message="prometheus: i below three"}"#;
+
+  assert_eq!(
+    metric(
+      &rendered,
+      "runtime_contracts_evaluations_total",
+      "i below three"
+    ),
+    vec![format!("runtime_contracts_evaluations_total{labels} 4").as_str()]
+  );
+  assert_eq!(
+    metric(
+      &rendered,
+      "runtime_contracts_violations_total",
+      "i below three"
+    ),
+    vec![format!("runtime_contracts_violations_total{labels} 1").as_str()]
+  );
+  assert_eq!(
+    metric(
+      &rendered,
+      "runtime_contracts_skipped_total",
+      "i below three"
+    ),
+    vec![format!("runtime_contracts_skipped_total{labels} 0").as_str()]
+  );
+}
+
+#[test]
+fn declares_each_metric_once() {
+  let _ = check(|| true, "prometheus: declared");
+  let rendered = prometheus::render(&stats::snapshot());
+
+  for name in [
+    "runtime_contracts_evaluations_total",
+    "runtime_contracts_violations_total",
+    "runtime_contracts_unevaluated_total",
+    "runtime_contracts_skipped_total",
+  ] {
+    let declarations: Vec<_> = rendered
+      .lines()
+      .filter(|line| line.starts_with('#') && line.contains(name))
+      .collect();
+
+    assert_eq!(declarations.len(), 2);
+    assert_eq!(declarations[1], format!("# TYPE {name} counter"));
+  }
+}
+
+#[test]
+fn sums_sites_sharing_labels() {
+  let _ = check(|| false, "prometheus: shared");
+  let _ = check(|| false, "prometheus: shared");
+
+  let rendered = prometheus::render(&stats::snapshot());
+
+  assert_eq!(
+    metric(
+      &rendered,
+      "runtime_contracts_violations_total",
+      "prometheus: shared"
+    ),
+    vec![
+      r#"runtime_contracts_violations_total{kind="check",module="",message="prometheus: shared"} 2"#
+    ]
+  );
+}
+
+#[test]
+fn escapes_label_values() {
+  let _ = check(|| true, "prometheus: \"quoted\" \\ and\nsplit");
+
+  let rendered = prometheus::render(&stats::snapshot());
+
+  assert_eq!(
+    metric(&rendered, "runtime_contracts_evaluations_total", "quoted"),
+    vec![
+      r#"runtime_contracts_evaluations_total{kind="check",module="",message="prometheus: \"quoted\" \\ and\nsplit"} 1"#
+    ]
+  );
+}
+
+#[test]
+fn counts_unevaluated_contracts_apart_from_violations() {
+  for input in ["1", "-1", "one"] {
+    let _ = try_check(
+      || input.parse::<i32>().map(|i| i > 0),
+      "prometheus: parses as positive",
+    );
+  }
+
+  let rendered = prometheus::render(&stats::snapshot());
+  let labels = r#"{kind="check",module="",message="prometheus: parses as positive"}"#;
+  let lines: Vec<_> = rendered
+    .lines()
+    .filter(|line| line.contains("parses as positive"))
+    .collect();
+
+  assert_eq!(
+    lines,
+    vec![
+      format!("runtime_contracts_evaluations_total{labels} 3"),
+      format!("runtime_contracts_violations_total{labels} 1"),
+      format!("runtime_contracts_unevaluated_total{labels} 1"),
+      format!("runtime_contracts_skipped_total{labels} 0"),
+    ]
+  );
+}
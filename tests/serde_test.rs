@@ -0,0 +1,126 @@
+#![cfg(feature = "serde")]
+
+use pretty_assertions::assert_eq;
+use serde_json::json;
+
+use runtime_contracts::error::{
+  ContractFailure, ContractViolations, RuntimeContractError, SourceLocation,
+};
+use runtime_contracts::{context, requires, try_check, ContractKind};
+
+#[test]
+fn errors_serialize_with_a_tagged_kind() {
+  let failure = ContractFailure::new("amount must be positive")
+    .with_location(SourceLocation::new("src/accounts.rs", 10, 5).with_module_path("bank::accounts"))
+    .with_predicate("amount > 0")
+    .with_context("request_id", "7f3a")
+    .with_context("operation", "withdraw");
+  let err = RuntimeContractError::from_failure(ContractKind::Requires, failure);
+
+  assert_eq!(
+    serde_json::to_string(&err).unwrap(),
+    r#"{"kind":"requires","message":"amount must be positive","predicate":"amount > 0","location":{"file":"src/accounts.rs","line":10,"column":5,"module_path":"bank::accounts"},"context":{"request_id":"7f3a","operation":"withdraw"},"blame":{"party":"caller"}}"#
+  );
+}
+
+#[test]
+fn optional_fields_are_omitted() {
+  let err = RuntimeContractError::new(ContractKind::Ensures, "must be sorted");
+
+  assert_eq!(
+    serde_json::to_value(&err).unwrap(),
+    json!({ "kind": "ensures", "message": "must be sorted", "blame": { "party": "callee" } })
+  );
+}
+
+#[test]
+fn errors_round_trip() {
+  let _request = context::scope("request_id", 42);
+  let _operation = context::scope("operation", "deposit");
+  let err = requires!(1 > 2, "one must exceed two").unwrap_err();
+  let json = serde_json::to_string(&err).unwrap();
+
+  assert_eq!(
+    serde_json::from_str::<RuntimeContractError>(&json).unwrap(),
+    err
+  );
+}
+
+#[test]
+fn unevaluated_errors_record_their_cause() {
+  let err = try_check(|| "many".parse::<u8>().map(|n| n > 2), "must be several").unwrap_err();
+  let json = serde_json::to_value(&err).unwrap();
+
+  assert_eq!(json["cause"], "invalid digit found in string");
+
+  let deserialized = serde_json::from_value::<RuntimeContractError>(json).unwrap();
+
+  assert_eq!(deserialized, err);
+  assert!(!deserialized.is_violation());
+  assert_eq!(
+    std::error::Error::source(&deserialized)
+      .unwrap()
+      .to_string(),
+    "invalid digit found in string"
+  );
+}
+
+#[test]
+fn deserializing_ignores_unknown_fields_and_rejects_unknown_kinds() {
+  let err: RuntimeContractError = serde_json::from_value(json!({
+    "kind": "check",
+    "message": "balance must not be negative",
+    "severity": "high",
+  }))
+  .unwrap();
+
+  assert_eq!(
+    err,
+    RuntimeContractError::new(ContractKind::Check, "balance must not be negative")
+  );
+  assert!(serde_json::from_value::<RuntimeContractError>(
+    json!({ "kind": "invariant", "message": "m" })
+  )
+  .is_err());
+  assert!(serde_json::from_value::<RuntimeContractError>(json!({ "kind": "check" })).is_err());
+}
+
+#[test]
+fn blame_labels_round_trip() {
+  let failure =
+    ContractFailure::new("balance must not be negative").with_blame_label("ledger-team");
+  let err = RuntimeContractError::from_failure(ContractKind::Check, failure);
+  let json = serde_json::to_value(&err).unwrap();
+
+  assert_eq!(
+    json["blame"],
+    json!({ "party": "module", "label": "ledger-team" })
+  );
+  assert_eq!(
+    serde_json::from_value::<RuntimeContractError>(json).unwrap(),
+    err
+  );
+}
+
+#[test]
+fn violations_serialize_as_arrays() {
+  let violations: ContractViolations = [
+    RuntimeContractError::new(ContractKind::Requires, "first"),
+    RuntimeContractError::new(ContractKind::Check, "second"),
+  ]
+  .into_iter()
+  .collect();
+  let json = serde_json::to_value(&violations).unwrap();
+
+  assert_eq!(
+    json,
+    json!([
+      { "kind": "requires", "message": "first", "blame": { "party": "caller" } },
+      { "kind": "check", "message": "second", "blame": { "party": "module" } },
+    ])
+  );
+  assert_eq!(
+    serde_json::from_value::<ContractViolations>(json).unwrap(),
+    violations
+  );
+}
@@ -0,0 +1,85 @@
+use pretty_assertions::assert_eq;
+
+use runtime_contracts::error::ContractViolations;
+use runtime_contracts::set::ContractSet;
+use runtime_contracts::{requires, ContractKind, Result, RuntimeContract};
+
+#[test]
+fn set_passes_when_every_contract_holds() {
+  let res = ContractSet::new()
+    .requires(|| true, "should always pass")
+    .ensures(&1, |v| *v == 1, "should always pass")
+    .check(|| true, "should always pass")
+    .finish();
+
+  assert_eq!(res, Ok(()));
+}
+
+#[test]
+fn set_collects_every_violation_in_order() {
+  let positive = RuntimeContract::requires(|i: &i32| *i > 0, "positive");
+
+  let violations = ContractSet::new()
+    .requires(|| false, "first")
+    .check(|| true, "passes")
+    .ensures("", |s: &str| !s.is_empty(), "second")
+    .verify(&positive, &-1)
+    .finish()
+    .unwrap_err();
+
+  let kinds: Vec<_> = violations.iter().map(|err| err.kind()).collect();
+  let messages: Vec<_> = violations.iter().map(|err| err.message()).collect();
+
+  assert_eq!(violations.len(), 3);
+  assert_eq!(
+    kinds,
+    vec![
+      ContractKind::Requires,
+      ContractKind::Ensures,
+      ContractKind::Requires
+    ]
+  );
+  assert_eq!(messages, vec!["first", "second", "positive"]);
+}
+
+#[test]
+fn set_records_existing_results() {
+  let set = ContractSet::new()
+    .record(requires(|| false, "recorded"))
+    .record(Ok::<_, runtime_contracts::error::RuntimeContractError>(5));
+
+  assert!(set.is_violated());
+  assert_eq!(set.finish().unwrap_err().len(), 1);
+}
+
+#[test]
+fn violations_display_every_error() {
+  let violations = ContractSet::new()
+    .requires(|| false, "first")
+    .requires(|| false, "second")
+    .finish()
+    .unwrap_err();
+
+  let display = violations.to_string();
+
+  assert!(display.starts_with("2 contract violations: requires validation failed: first at "));
+  assert!(display.contains("; requires validation failed: second at "));
+}
+
+fn validate(age: u32) -> Result<u32, ContractViolations> {
+  requires(|| age > 0, "age must be positive")?;
+
+  ContractSet::new()
+    .requires(|| age >= 18, "must be an adult")
+    .requires(|| age.to_string().len() == 2, "must have two digits")
+    .finish()?;
+
+  Ok(age)
+}
+
+#[test]
+fn violations_interoperate_with_single_errors() {
+  assert_eq!(validate(20), Ok(20));
+  assert_eq!(validate(0).unwrap_err().len(), 1);
+  assert_eq!(validate(3).unwrap_err().len(), 2);
+}
@@ -0,0 +1,223 @@
+mod common;
+
+use std::thread;
+
+use pretty_assertions::assert_eq;
+
+use runtime_contracts::cost::{self, Cost, Gate};
+use runtime_contracts::policy::{self, AssertionLevel};
+use runtime_contracts::stats::{self, SiteStats, StatsSnapshot};
+use runtime_contracts::{
+  check, ensures, requires, requires_or, try_check, ContractKind, RuntimeContract,
+};
+
+fn site<'a>(snapshot: &'a StatsSnapshot, message: &str) -> &'a SiteStats {
+  snapshot
+    .iter()
+    .find(|site| site.message() == message)
+    .unwrap_or_else(|| panic!("no site for {message:?}"))
+}
+
+fn counts(site: &SiteStats) -> (u64, u64, u64, u64) {
+  (
+    site.evaluated(),
+    site.passed(),
+    site.failed(),
+    site.skipped(),
+  )
+}
+
+#[test]
+fn counts_passes_and_failures_per_site() {
+  let _guard = common::serial();
+
+  for i in 0..5 {
+    let _ = requires(|| i % 2 == 0, "stats: i must be even");
+  }
+
+  let line = line!() - 3;
+  let snapshot = runtime_contracts::stats();
+  let site = site(&snapshot, "stats: i must be even");
+
+  assert_eq!(counts(site), (5, 3, 2, 0));
+  assert_eq!(site.kind(), ContractKind::Requires);
+  assert_eq!(site.location().file(), file!());
+  assert_eq!(site.location().line(), line);
+}
+
+#[test]
+fn distinguishes_sites_by_location_and_message() {
+  let _guard = common::serial();
+
+  for _ in 0..2 {
+    let _ = check(|| true, "stats: first site");
+  }
+
+  let _ = ensures(1, |i| *i > 1, "stats: second site");
+
+  for message in ["stats: shared location a", "stats: shared location b"] {
+    let _ = check(|| false, message);
+  }
+
+  let snapshot = stats::snapshot();
+
+  assert_eq!(counts(site(&snapshot, "stats: first site")), (2, 2, 0, 0));
+  assert_eq!(counts(site(&snapshot, "stats: second site")), (1, 0, 1, 0));
+  assert_eq!(
+    counts(site(&snapshot, "stats: shared location a")),
+    (1, 0, 1, 0)
+  );
+  assert_eq!(
+    counts(site(&snapshot, "stats: shared location b")),
+    (1, 0, 1, 0)
+  );
+}
+
+#[test]
+fn counts_skipped_evaluations() {
+  let _guard = common::serial();
+
+  let expensive =
+    RuntimeContract::check(|i: &i32| *i > 0, "stats: expensive").with_cost(Cost::Expensive);
+
+  for gate in [Gate::Never, Gate::Always] {
+    cost::set_gate(Cost::Expensive, gate);
+    let _ = expensive.verify(&1);
+  }
+
+  policy::set_level(AssertionLevel::None);
+  let _ = requires(|| false, "stats: below level");
+  policy::set_level(AssertionLevel::All);
+
+  let snapshot = stats::snapshot();
+
+  assert_eq!(counts(site(&snapshot, "stats: expensive")), (1, 1, 0, 1));
+  assert_eq!(counts(site(&snapshot, "stats: below level")), (0, 0, 0, 1));
+}
+
+#[test]
+fn aggregates_across_threads() {
+  let _guard = common::serial();
+
+  let evaluate = |i: u32| {
+    let _ = check(|| i < 30, "stats: from many threads");
+  };
+
+  thread::scope(|scope| {
+    for t in 0..4 {
+      scope.spawn(move || (0..10).for_each(|i| evaluate(t * 10 + i)));
+    }
+  });
+
+  let snapshot = stats::snapshot();
+
+  assert_eq!(
+    counts(site(&snapshot, "stats: from many threads")),
+    (40, 30, 10, 0)
+  );
+}
+
+#[test]
+fn take_resets_the_counters() {
+  let _guard = common::serial();
+
+  let evaluate = || {
+    let _ = check(|| true, "stats: taken");
+  };
+
+  evaluate();
+  evaluate();
+
+  let taken = stats::take();
+
+  assert_eq!(counts(site(&taken, "stats: taken")), (2, 2, 0, 0));
+  assert_eq!(
+    counts(site(&stats::snapshot(), "stats: taken")),
+    (0, 0, 0, 0)
+  );
+
+  evaluate();
+  assert_eq!(
+    counts(site(&stats::snapshot(), "stats: taken")),
+    (1, 1, 0, 0)
+  );
+
+  stats::reset();
+  assert!(stats::snapshot()
+    .iter()
+    .all(|site| site.evaluated() == 0 && site.skipped() == 0));
+}
+
+#[test]
+fn custom_errors_are_only_built_on_failure() {
+  let _guard = common::serial();
+
+  let built = std::cell::Cell::new(0);
+  let build = || {
+    built.set(built.get() + 1);
+    "stats: custom error"
+  };
+
+  let _ = requires_or(|| true, build);
+  let line = line!() - 1;
+
+  assert_eq!(built.get(), 0);
+
+  let snapshot = stats::snapshot();
+  let site = snapshot
+    .iter()
+    .find(|site| site.location().file() == file!() && site.location().line() == line)
+    .unwrap();
+
+  assert_eq!(site.message(), "");
+  assert_eq!(counts(site), (1, 1, 0, 0));
+}
+
+#[test]
+fn caps_the_messages_kept_per_location() {
+  let _guard = common::serial();
+
+  let evaluate = |i: usize| {
+    let _ = check(|| i.is_multiple_of(2), format!("stats: capped {i}"));
+  };
+
+  for i in 0..stats::MAX_MESSAGES + 4 {
+    evaluate(i);
+  }
+
+  // A message which has its own counters keeps them, even on a thread which has not seen it before.
+  thread::scope(|scope| {
+    scope.spawn(|| evaluate(0));
+  });
+  evaluate(stats::MAX_MESSAGES);
+
+  let line = line!() - 13;
+  let snapshot = stats::snapshot();
+  let sites: Vec<_> = snapshot
+    .iter()
+    .filter(|site| site.location().file() == file!() && site.location().line() == line)
+    .collect();
+
+  assert_eq!(sites.len(), stats::MAX_MESSAGES + 1);
+  assert_eq!(counts(site(&snapshot, "stats: capped 0")), (2, 2, 0, 0));
+  assert_eq!(counts(site(&snapshot, "stats: capped 1")), (1, 0, 1, 0));
+  assert_eq!(counts(site(&snapshot, stats::OTHER_MESSAGES)), (5, 3, 2, 0));
+}
+
+#[test]
+fn counts_unevaluated_contracts_apart_from_failures() {
+  let _guard = common::serial();
+
+  for input in ["1", "-1", "one"] {
+    let _ = try_check(
+      || input.parse::<i32>().map(|i| i > 0),
+      "stats: parses as positive",
+    );
+  }
+
+  let snapshot = stats::snapshot();
+  let site = site(&snapshot, "stats: parses as positive");
+
+  assert_eq!(counts(site), (3, 1, 1, 0));
+  assert_eq!(site.errored(), 1);
+}
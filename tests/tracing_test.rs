@@ -0,0 +1,155 @@
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use pretty_assertions::assert_eq;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+use runtime_contracts::{requires, requires_or, trace};
+
+type Fields = BTreeMap<&'static str, String>;
+
+/// A subscriber which records the level and fields of every event emitted by this crate.
+#[derive(Clone, Default)]
+struct Recorder {
+  events: Arc<Mutex<Vec<(Level, Fields)>>>,
+}
+
+struct FieldVisitor<'a>(&'a mut Fields);
+
+impl Visit for FieldVisitor<'_> {
+  fn record_str(&mut self, field: &Field, value: &str) {
+    self.0.insert(field.name(), value.to_string());
+  }
+
+  fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+    self.0.insert(field.name(), format!("{value:?}"));
+  }
+}
+
+impl Subscriber for Recorder {
+  fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+    metadata.target() == "runtime_contracts"
+  }
+
+  fn new_span(&self, _span: &Attributes<'_>) -> Id {
+    Id::from_u64(1)
+  }
+
+  fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+  fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+  fn event(&self, event: &Event<'_>) {
+    let mut fields = Fields::new();
+
+    event.record(&mut FieldVisitor(&mut fields));
+    self
+      .events
+      .lock()
+      .unwrap()
+      .push((*event.metadata().level(), fields));
+  }
+
+  fn enter(&self, _span: &Id) {}
+
+  fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn evaluations_and_violations_emit_events() {
+  let _guard = common::serial();
+  let recorder = Recorder::default();
+  let line = line!() + 4;
+
+  tracing::subscriber::with_default(recorder.clone(), || {
+    let _ = requires(|| true, "passes");
+    let _ = requires(|| false, "fails");
+  });
+
+  let events = recorder.events.lock().unwrap();
+  let outcomes: Vec<_> = events
+    .iter()
+    .map(|(level, fields)| {
+      (
+        *level,
+        fields["contract_message"].as_str(),
+        fields.get("outcome"),
+      )
+    })
+    .collect();
+
+  assert_eq!(
+    outcomes,
+    vec![
+      (Level::TRACE, "passes", Some(&"passed".to_string())),
+      (Level::TRACE, "fails", Some(&"failed".to_string())),
+      (Level::ERROR, "fails", None),
+    ]
+  );
+
+  let violation = &events[2].1;
+
+  assert_eq!(violation["message"], "contract violated");
+  assert_eq!(violation["kind"], "requires");
+  assert_eq!(violation["file"], file!());
+  assert_eq!(violation["line"], line.to_string());
+  assert!(!violation.contains_key("predicate"));
+  assert!(!violation.contains_key("module_path"));
+  assert_eq!(events[0].1["message"], "contract evaluated");
+}
+
+#[test]
+fn custom_errors_are_only_built_on_failure() {
+  let _guard = common::serial();
+  let recorder = Recorder::default();
+  let built = Cell::new(0);
+  let build = || {
+    built.set(built.get() + 1);
+    "custom error"
+  };
+
+  tracing::subscriber::with_default(recorder.clone(), || {
+    for _ in 0..3 {
+      let _ = requires_or(|| true, build);
+    }
+  });
+
+  assert_eq!(built.get(), 0);
+
+  tracing::subscriber::with_default(recorder.clone(), || {
+    let _ = requires_or(|| false, build);
+  });
+
+  let events = recorder.events.lock().unwrap();
+
+  assert_eq!(built.get(), 1);
+  assert!(!events[0].1.contains_key("contract_message"));
+  assert_eq!(events[3].1["contract_message"], "custom error");
+}
+
+#[test]
+fn violation_level_is_configurable() {
+  let _guard = common::serial();
+  let recorder = Recorder::default();
+
+  trace::set_violation_level(Level::WARN);
+
+  tracing::subscriber::with_default(recorder.clone(), || {
+    let _ = requires(|| false, "fails");
+  });
+
+  trace::set_violation_level(Level::ERROR);
+
+  let events = recorder.events.lock().unwrap();
+
+  assert_eq!(trace::violation_level(), Level::ERROR);
+  assert_eq!(events.last().unwrap().0, Level::WARN);
+}
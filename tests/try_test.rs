@@ -0,0 +1,117 @@
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
+
+use pretty_assertions::assert_eq;
+
+use runtime_contracts::error::{ContractFailure, RuntimeContractError};
+use runtime_contracts::{try_check, try_ensures, try_requires, ContractKind};
+
+fn parses_as_positive(s: &str) -> Result<bool, ParseIntError> {
+  s.parse::<i64>().map(|i| i > 0)
+}
+
+#[test]
+fn predicates_which_succeed_decide_whether_the_contract_holds() {
+  assert_eq!(
+    try_requires(|| parses_as_positive("5"), "must be positive"),
+    Ok(())
+  );
+  assert_eq!(
+    try_ensures("7", |s| parses_as_positive(s), "must be positive"),
+    Ok("7")
+  );
+  assert_eq!(
+    try_check(|| parses_as_positive("9"), "must be positive"),
+    Ok(())
+  );
+
+  let err = try_requires(|| parses_as_positive("-5"), "must be positive").unwrap_err();
+
+  assert!(err.is_violation());
+  assert!(matches!(err, RuntimeContractError::RequiresFailure(_)));
+  assert!(err.source().is_none());
+}
+
+#[test]
+fn predicates_which_fail_leave_the_contract_unevaluated() {
+  let err = try_requires(|| parses_as_positive("five"), "must be positive").unwrap_err();
+
+  assert!(!err.is_violation());
+  assert_eq!(err.kind(), ContractKind::Requires);
+  assert_eq!(err.message(), "must be positive");
+  assert!(matches!(
+    err,
+    RuntimeContractError::EvaluationFailure { .. }
+  ));
+}
+
+#[test]
+fn the_predicates_error_is_the_source() {
+  let err = try_check(|| parses_as_positive(""), "must be positive").unwrap_err();
+  let source = err.source().unwrap();
+
+  assert_eq!(source.to_string(), "cannot parse integer from empty string");
+  assert!(source.downcast_ref::<ParseIntError>().is_some());
+}
+
+#[test]
+fn predicates_can_return_boxed_errors() {
+  let within_limit = |path: &str| -> Result<bool, Box<dyn Error + Send + Sync>> {
+    let limit: u64 = "1024".parse()?;
+
+    Ok(std::fs::metadata(path)?.len() <= limit)
+  };
+
+  let err = try_requires(|| within_limit("/no/such/file"), "file must be small").unwrap_err();
+  let source = err.source().unwrap();
+
+  assert!(!err.is_violation());
+  assert_eq!(
+    source.downcast_ref::<std::io::Error>().unwrap().kind(),
+    std::io::ErrorKind::NotFound
+  );
+}
+
+#[test]
+fn unevaluated_contracts_keep_their_kind_and_location() {
+  let line = line!() + 1;
+  let err = try_ensures("x", |s| parses_as_positive(s), "must be positive").unwrap_err();
+
+  assert_eq!(err.kind(), ContractKind::Ensures);
+  assert_eq!(err.location().unwrap().file(), file!());
+  assert_eq!(err.location().unwrap().line(), line);
+  assert!(err
+    .to_string()
+    .starts_with("ensures validation could not be performed: must be positive at "));
+}
+
+#[derive(Debug)]
+struct Unreadable;
+
+impl fmt::Display for Unreadable {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str("config is unreadable")
+  }
+}
+
+impl Error for Unreadable {}
+
+#[test]
+fn unevaluated_errors_compare_by_message() {
+  let unevaluated = |source| {
+    RuntimeContractError::unevaluated(
+      ContractKind::Check,
+      ContractFailure::new("must load"),
+      source,
+    )
+  };
+  let err = unevaluated(Unreadable);
+
+  assert_eq!(err.clone(), err);
+  assert_eq!(unevaluated(Unreadable), err);
+  assert_ne!(
+    RuntimeContractError::new(ContractKind::Check, "must load"),
+    err
+  );
+}